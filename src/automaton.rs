@@ -0,0 +1,270 @@
+use core::fmt;
+
+use crate::bitset::{self, DynBitset};
+
+/// Birth/survival rule of a two-state cellular automaton on the Moore neighborhood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    // Bit `c` is set if a cell with `c` live neighbors is born (resp. survives).
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23.
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+    };
+
+    /// Create a rule from the neighbor counts (0 to 8) that give birth to a dead cell and that
+    /// keep a live cell alive.
+    pub fn new(birth: &[u8], survival: &[u8]) -> Self {
+        let mask = |counts: &[u8]| {
+            counts.iter().fold(0, |acc, &c| {
+                assert!(c <= 8, "neighbor count out of range");
+                acc | (1 << c)
+            })
+        };
+        Rule {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+}
+
+/// A bounded two-dimensional board of cells whose rows are bitsets, so that a generation is
+/// computed 64 cells at a time.
+///
+/// Cells outside the board are dead. If a pattern can grow, make room with [`BitBoard::pad`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    width: usize,
+    height: usize,
+    rows: Vec<DynBitset>,
+}
+
+impl BitBoard {
+    pub fn new(width: usize, height: usize) -> Self {
+        BitBoard {
+            width,
+            height,
+            rows: vec![DynBitset::new(); height],
+        }
+    }
+
+    /// Parse a character grid, treating every `alive` byte as a live cell.
+    /// The width is the length of the longest line.
+    pub fn parse(input: &[u8], alive: u8) -> Self {
+        let lines: Vec<&[u8]> = input
+            .split(|&b| b == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .filter(|line| !line.is_empty())
+            .collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let mut board = BitBoard::new(width, lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, &b) in line.iter().enumerate() {
+                if b == alive {
+                    board.set(x, y);
+                }
+            }
+        }
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Make cell `(x, y)` alive.
+    pub fn set(&mut self, x: usize, y: usize) {
+        assert!(x < self.width, "x out of bounds");
+        self.rows[y].set(x);
+    }
+
+    /// Test if cell `(x, y)` is alive. Cells outside the board are dead.
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        self.rows.get(y).is_some_and(|row| row.is_set(x))
+    }
+
+    /// Kill cell `(x, y)`.
+    pub fn clear(&mut self, x: usize, y: usize) {
+        self.rows[y].clear(x);
+    }
+
+    /// Number of live cells.
+    pub fn population(&self) -> usize {
+        self.rows.iter().map(|row| row.count()).sum()
+    }
+
+    /// Iterate over the live cells as `(x, y)`, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().map(move |x| (x, y)))
+    }
+
+    /// Add `margin` dead cells on every side of the board.
+    pub fn pad(&mut self, margin: usize) {
+        let mut board = BitBoard::new(self.width + 2 * margin, self.height + 2 * margin);
+        for (x, y) in self.iter() {
+            board.set(x + margin, y + margin);
+        }
+        *self = board;
+    }
+
+    /// Advance the board by one generation.
+    pub fn step(&mut self, rule: Rule) {
+        let words = self.width.div_ceil(u64::BITS as usize);
+        let empty = DynBitset::new();
+        let mut rows = Vec::with_capacity(self.height);
+        for y in 0..self.height {
+            let above = if y > 0 { &self.rows[y - 1] } else { &empty };
+            let below = self.rows.get(y + 1).unwrap_or(&empty);
+            let current = &self.rows[y];
+            let row: Vec<u64> = (0..words)
+                .map(|i| {
+                    // Bit-sliced counter: `planes[j]` holds bit j of each cell's neighbor count.
+                    let mut planes = [0; 4];
+                    for neighbor in [above, below] {
+                        add(&mut planes, shifted_left(neighbor.words(), i));
+                        add(&mut planes, word(neighbor.words(), i));
+                        add(&mut planes, shifted_right(neighbor.words(), i));
+                    }
+                    add(&mut planes, shifted_left(current.words(), i));
+                    add(&mut planes, shifted_right(current.words(), i));
+
+                    let alive = word(current.words(), i);
+                    let mut next = 0;
+                    for count in 0..=8 {
+                        let born = rule.birth & (1 << count) != 0;
+                        let survives = rule.survival & (1 << count) != 0;
+                        if !born && !survives {
+                            continue;
+                        }
+                        let mut matches = u64::MAX;
+                        for (j, plane) in planes.iter().enumerate() {
+                            matches &= if count & (1 << j) != 0 {
+                                *plane
+                            } else {
+                                !plane
+                            };
+                        }
+                        if born {
+                            next |= matches & !alive;
+                        }
+                        if survives {
+                            next |= matches & alive;
+                        }
+                    }
+                    next & bitset::universe_word(i, self.width)
+                })
+                .collect();
+            rows.push(DynBitset::from_words(row));
+        }
+        self.rows = rows;
+    }
+}
+
+impl fmt::Display for BitBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            for x in 0..self.width {
+                write!(f, "{}", if row.is_set(x) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Add a one-bit value to every lane of the bit-sliced counter.
+fn add(planes: &mut [u64; 4], mut carry: u64) {
+    for plane in planes.iter_mut() {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+fn word(words: &[u64], i: usize) -> u64 {
+    words.get(i).copied().unwrap_or(0)
+}
+
+/// Word `i` of the row moved one position up, so that every cell sees its left neighbor.
+fn shifted_left(words: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 { word(words, i - 1) >> 63 } else { 0 };
+    (word(words, i) << 1) | carry
+}
+
+/// Word `i` of the row moved one position down, so that every cell sees its right neighbor.
+fn shifted_right(words: &[u64], i: usize) -> u64 {
+    (word(words, i) >> 1) | (word(words, i + 1) << 63)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker() {
+        let mut board = BitBoard::parse(b".....\n..#..\n..#..\n..#..\n.....\n", b'#');
+        assert_eq!(3, board.population());
+        board.step(Rule::CONWAY);
+        assert_eq!(".....\n.....\n.###.\n.....\n.....\n", board.to_string());
+        board.step(Rule::CONWAY);
+        assert_eq!(
+            vec![(2, 1), (2, 2), (2, 3)],
+            board.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_glider_across_words() {
+        let mut board = BitBoard::new(140, 8);
+        for (x, y) in [(61, 0), (62, 1), (60, 2), (61, 2), (62, 2)] {
+            board.set(x, y);
+        }
+        for _ in 0..12 {
+            board.step(Rule::CONWAY);
+        }
+        // A glider moves one cell diagonally every four generations.
+        let cells: Vec<(usize, usize)> = board.iter().collect();
+        assert_eq!(vec![(64, 3), (65, 4), (63, 5), (64, 5), (65, 5)], cells);
+    }
+
+    #[test]
+    fn test_edges_are_dead() {
+        let mut board = BitBoard::parse(b"##\n##\n", b'#');
+        board.step(Rule::new(&[1, 2, 3], &[]));
+        assert_eq!(0, board.population());
+        board.pad(1);
+        assert_eq!((4, 4), (board.width(), board.height()));
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let board = BitBoard::parse(b"#.#\r\n.#.\r\n", b'#');
+        assert_eq!((3, 2), (board.width(), board.height()));
+        assert_eq!(BitBoard::parse(b"#.#\n.#.\n", b'#'), board);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rule_count_out_of_range() {
+        Rule::new(&[3], &[9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_outside_board() {
+        let mut board = BitBoard::new(3, 1);
+        board.set(10, 0);
+    }
+}
@@ -1,3 +1,7 @@
+use core::ops;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub struct Bitset {
     bitset: u64,
@@ -48,6 +52,84 @@ impl Bitset {
             bitset: self.bitset | other.bitset,
         }
     }
+
+    /// Elements of `self` that are not in `other`.
+    pub fn difference(&self, other: Self) -> Bitset {
+        Bitset {
+            bitset: self.bitset & !other.bitset,
+        }
+    }
+
+    /// Elements in exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: Self) -> Bitset {
+        Bitset {
+            bitset: self.bitset ^ other.bitset,
+        }
+    }
+
+    /// Complement within the universe `0..n`.
+    pub fn complement(&self, n: usize) -> Bitset {
+        debug_assert!(n <= WORD_BITS);
+        Bitset {
+            bitset: !self.bitset & universe_word(0, n),
+        }
+    }
+
+    /// Test if every element of `self` is in `other`.
+    pub fn is_subset(&self, other: Self) -> bool {
+        self.bitset & !other.bitset == 0
+    }
+
+    /// Test if every element of `other` is in `self`.
+    pub fn is_superset(&self, other: Self) -> bool {
+        other.is_subset(*self)
+    }
+
+    /// Test if `self` and `other` have no elements in common.
+    pub fn is_disjoint(&self, other: Self) -> bool {
+        self.bitset & other.bitset == 0
+    }
+
+    /// Test if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.bitset == 0
+    }
+
+    /// Number of set bits.
+    pub fn count(&self) -> usize {
+        self.bitset.count_ones() as usize
+    }
+
+    /// Lowest set position.
+    pub fn first(&self) -> Option<u32> {
+        (self.bitset != 0).then(|| self.bitset.trailing_zeros())
+    }
+
+    /// Highest set position.
+    pub fn last(&self) -> Option<u32> {
+        (self.bitset != 0).then(|| u64::BITS - 1 - self.bitset.leading_zeros())
+    }
+
+    /// Iterate over all subsets of `self`, from `self` down to the empty set.
+    pub fn subsets(&self) -> SubsetIterator {
+        SubsetIterator {
+            mask: self.bitset,
+            next: Some(self.bitset),
+        }
+    }
+
+    /// Iterate over all supersets of `self` within the universe `0..n`.
+    pub fn supersets(&self, n: usize) -> SupersetIterator {
+        SupersetIterator {
+            base: self.bitset,
+            free: self.complement(n).subsets(),
+        }
+    }
+
+    /// Iterate over all subsets of `self` with exactly `k` elements, in increasing order.
+    pub fn combinations(&self, k: usize) -> CombinationIterator {
+        CombinationIterator::new(self.bitset, k)
+    }
 }
 
 /// Iterate over positions with 1 bit set.
@@ -73,6 +155,696 @@ impl Iterator for BitsetIterator {
     }
 }
 
+/// Iterate over all subsets of a bitset.
+pub struct SubsetIterator {
+    mask: u64,
+    next: Option<u64>,
+}
+
+impl Iterator for SubsetIterator {
+    type Item = Bitset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bitset = self.next?;
+        // Subtracting one flips the lowest set bit and everything below it; masking drops the
+        // bits outside the set, which yields the next smaller subset.
+        self.next = (bitset != 0).then(|| (bitset - 1) & self.mask);
+        Some(Bitset { bitset })
+    }
+}
+
+/// Iterate over all supersets of a bitset within a universe.
+pub struct SupersetIterator {
+    base: u64,
+    free: SubsetIterator,
+}
+
+impl Iterator for SupersetIterator {
+    type Item = Bitset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.free.next().map(|free| Bitset {
+            bitset: free.bitset | self.base,
+        })
+    }
+}
+
+/// Iterate over all subsets of a bitset with a fixed number of elements.
+pub struct CombinationIterator {
+    mask: u64,
+    // Combination over the positions `0..mask.count_ones()`, widened so that a full 64 bit mask
+    // can be detected as exhausted.
+    state: u128,
+    limit: u128,
+    done: bool,
+}
+
+impl CombinationIterator {
+    fn new(mask: u64, k: usize) -> Self {
+        let n = mask.count_ones() as usize;
+        let done = k > n;
+        CombinationIterator {
+            mask,
+            state: if done { 0 } else { (1 << k) - 1 },
+            limit: 1 << n,
+            done,
+        }
+    }
+}
+
+impl Iterator for CombinationIterator {
+    type Item = Bitset;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.state >= self.limit {
+            return None;
+        }
+        let current = self.state as u64;
+        if self.state == 0 {
+            self.done = true;
+        } else {
+            // Gosper's hack: the next larger integer with the same number of set bits.
+            let c = self.state & self.state.wrapping_neg();
+            let r = self.state + c;
+            self.state = (((r ^ self.state) >> 2) / c) | r;
+        }
+        Some(Bitset {
+            bitset: deposit(current, self.mask),
+        })
+    }
+}
+
+/// Iterate over positions with 1 bit set across a slice of words.
+pub struct WordsIterator<'a> {
+    words: std::iter::Enumerate<std::slice::Iter<'a, u64>>,
+    offset: usize,
+    current: BitsetIterator,
+}
+
+impl<'a> WordsIterator<'a> {
+    fn new(words: &'a [u64]) -> Self {
+        WordsIterator {
+            words: words.iter().enumerate(),
+            offset: 0,
+            current: BitsetIterator { bitset: 0 },
+        }
+    }
+}
+
+impl Iterator for WordsIterator<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(r) = self.current.next() {
+                return Some(self.offset + r as usize);
+            }
+            let (i, &word) = self.words.next()?;
+            self.offset = i * WORD_BITS;
+            self.current = BitsetIterator { bitset: word };
+        }
+    }
+}
+
+/// A growable bitset backed by a `Vec<u64>`.
+///
+/// Words are allocated on demand, so any position can be set. Trailing zero words are dropped
+/// eagerly, which keeps `Eq` and `Hash` independent of the allocation history.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
+pub struct DynBitset {
+    words: Vec<u64>,
+}
+
+impl DynBitset {
+    pub fn new() -> Self {
+        DynBitset { words: Vec::new() }
+    }
+
+    /// Create an empty bitset with room for `bits` positions before reallocating.
+    pub fn with_capacity(bits: usize) -> Self {
+        DynBitset {
+            words: Vec::with_capacity(bits.div_ceil(WORD_BITS)),
+        }
+    }
+
+    /// Set bit at position `k`.
+    pub fn set(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        if i >= self.words.len() {
+            self.words.resize(i + 1, 0);
+        }
+        self.words[i] |= mask;
+    }
+
+    /// Test if bit at position k is set.
+    pub fn is_set(&self, k: usize) -> bool {
+        let (i, mask) = locate(k);
+        self.words.get(i).is_some_and(|w| w & mask != 0)
+    }
+
+    /// Clear bit at position `k`.
+    pub fn clear(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        if let Some(w) = self.words.get_mut(i) {
+            *w &= !mask;
+            self.trim();
+        }
+    }
+
+    /// Toggle bit at position `k`.
+    pub fn toggle(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        if i >= self.words.len() {
+            self.words.resize(i + 1, 0);
+        }
+        self.words[i] ^= mask;
+        self.trim();
+    }
+
+    /// Iterate over positions where the bit is set.
+    pub fn iter(&self) -> WordsIterator<'_> {
+        WordsIterator::new(&self.words)
+    }
+
+    /// Intersect with another bitset.
+    pub fn intersect(&self, other: &Self) -> DynBitset {
+        let mut result = DynBitset {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(a, b)| a & b)
+                .collect(),
+        };
+        result.trim();
+        result
+    }
+
+    /// Union with another bitset.
+    pub fn union(&self, other: &Self) -> DynBitset {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = long.words.clone();
+        for (w, s) in words.iter_mut().zip(short.words.iter()) {
+            *w |= s;
+        }
+        DynBitset { words }
+    }
+
+    /// Elements of `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> DynBitset {
+        let mut result = self.clone();
+        for (w, o) in result.words.iter_mut().zip(other.words.iter()) {
+            *w &= !o;
+        }
+        result.trim();
+        result
+    }
+
+    /// Elements in exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: &Self) -> DynBitset {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut result = long.clone();
+        for (w, s) in result.words.iter_mut().zip(short.words.iter()) {
+            *w ^= s;
+        }
+        result.trim();
+        result
+    }
+
+    /// Complement within the universe `0..n`.
+    pub fn complement(&self, n: usize) -> DynBitset {
+        let mut result = DynBitset {
+            words: (0..n.div_ceil(WORD_BITS))
+                .map(|i| !self.words.get(i).copied().unwrap_or(0) & universe_word(i, n))
+                .collect(),
+        };
+        result.trim();
+        result
+    }
+
+    /// Test if every element of `self` is in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        words_is_subset(&self.words, &other.words)
+    }
+
+    /// Test if every element of `other` is in `self`.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Test if `self` and `other` have no elements in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        words_is_disjoint(&self.words, &other.words)
+    }
+
+    /// Test if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Number of set bits.
+    pub fn count(&self) -> usize {
+        words_count(&self.words)
+    }
+
+    /// Lowest set position.
+    pub fn first(&self) -> Option<usize> {
+        words_first(&self.words)
+    }
+
+    /// Highest set position.
+    pub fn last(&self) -> Option<usize> {
+        words_last(&self.words)
+    }
+
+    /// Build a bitset from its words, least significant first.
+    pub(crate) fn from_words(words: Vec<u64>) -> Self {
+        let mut result = DynBitset { words };
+        result.trim();
+        result
+    }
+
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Drop trailing zero words.
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DynBitset {
+    type Item = usize;
+    type IntoIter = WordsIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A fixed-width bitset of `WORDS * 64` bits stored inline.
+///
+/// Unlike [`DynBitset`] it is `Copy`, which makes it a cheap memoization key for states that
+/// need more than 64 bits. Setting a position `>= Self::BITS` panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedBitset<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const WORDS: usize> FixedBitset<WORDS> {
+    /// Number of positions the bitset can hold.
+    pub const BITS: usize = WORDS * WORD_BITS;
+
+    pub fn new() -> Self {
+        FixedBitset { words: [0; WORDS] }
+    }
+
+    /// Set bit at position `k`.
+    pub fn set(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        self.words[i] |= mask;
+    }
+
+    /// Test if bit at position k is set.
+    pub fn is_set(&self, k: usize) -> bool {
+        let (i, mask) = locate(k);
+        (self.words[i] & mask) != 0
+    }
+
+    /// Clear bit at position `k`.
+    pub fn clear(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        self.words[i] &= !mask;
+    }
+
+    /// Toggle bit at position `k`.
+    pub fn toggle(&mut self, k: usize) {
+        let (i, mask) = locate(k);
+        self.words[i] ^= mask;
+    }
+
+    /// Iterate over positions where the bit is set.
+    pub fn iter(&self) -> WordsIterator<'_> {
+        WordsIterator::new(&self.words)
+    }
+
+    /// Intersect with another bitset.
+    pub fn intersect(&self, other: Self) -> FixedBitset<WORDS> {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words) {
+            *w &= o;
+        }
+        result
+    }
+
+    /// Union with another bitset.
+    pub fn union(&self, other: Self) -> FixedBitset<WORDS> {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words) {
+            *w |= o;
+        }
+        result
+    }
+
+    /// Elements of `self` that are not in `other`.
+    pub fn difference(&self, other: Self) -> FixedBitset<WORDS> {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words) {
+            *w &= !o;
+        }
+        result
+    }
+
+    /// Elements in exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: Self) -> FixedBitset<WORDS> {
+        let mut result = *self;
+        for (w, o) in result.words.iter_mut().zip(other.words) {
+            *w ^= o;
+        }
+        result
+    }
+
+    /// Complement within the universe `0..n`.
+    pub fn complement(&self, n: usize) -> FixedBitset<WORDS> {
+        debug_assert!(n <= Self::BITS);
+        let mut result = *self;
+        for (i, w) in result.words.iter_mut().enumerate() {
+            *w = !*w & universe_word(i, n);
+        }
+        result
+    }
+
+    /// Test if every element of `self` is in `other`.
+    pub fn is_subset(&self, other: Self) -> bool {
+        words_is_subset(&self.words, &other.words)
+    }
+
+    /// Test if every element of `other` is in `self`.
+    pub fn is_superset(&self, other: Self) -> bool {
+        other.is_subset(*self)
+    }
+
+    /// Test if `self` and `other` have no elements in common.
+    pub fn is_disjoint(&self, other: Self) -> bool {
+        words_is_disjoint(&self.words, &other.words)
+    }
+
+    /// Test if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Number of set bits.
+    pub fn count(&self) -> usize {
+        words_count(&self.words)
+    }
+
+    /// Lowest set position.
+    pub fn first(&self) -> Option<usize> {
+        words_first(&self.words)
+    }
+
+    /// Highest set position.
+    pub fn last(&self) -> Option<usize> {
+        words_last(&self.words)
+    }
+}
+
+impl<const WORDS: usize> Default for FixedBitset<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a FixedBitset<WORDS> {
+    type Item = usize;
+    type IntoIter = WordsIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Operators for the `Copy` bitsets, delegating to the named methods.
+macro_rules! impl_set_operators {
+    (impl [$($params:tt)*] $ty:ty) => {
+        impl<$($params)*> ops::BitAnd for $ty {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                self.intersect(rhs)
+            }
+        }
+
+        impl<$($params)*> ops::BitAndAssign for $ty {
+            fn bitand_assign(&mut self, rhs: Self) {
+                *self = self.intersect(rhs);
+            }
+        }
+
+        impl<$($params)*> ops::BitOr for $ty {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                self.union(rhs)
+            }
+        }
+
+        impl<$($params)*> ops::BitOrAssign for $ty {
+            fn bitor_assign(&mut self, rhs: Self) {
+                *self = self.union(rhs);
+            }
+        }
+
+        impl<$($params)*> ops::BitXor for $ty {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self::Output {
+                self.symmetric_difference(rhs)
+            }
+        }
+
+        impl<$($params)*> ops::BitXorAssign for $ty {
+            fn bitxor_assign(&mut self, rhs: Self) {
+                *self = self.symmetric_difference(rhs);
+            }
+        }
+
+        impl<$($params)*> ops::Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.difference(rhs)
+            }
+        }
+
+        impl<$($params)*> ops::SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = self.difference(rhs);
+            }
+        }
+
+        impl<$($params)*> FromIterator<usize> for $ty {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut result = Self::new();
+                result.extend(iter);
+                result
+            }
+        }
+
+        impl<$($params)*> Extend<usize> for $ty {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                for k in iter {
+                    self.set(k);
+                }
+            }
+        }
+    };
+}
+
+impl_set_operators!(impl [] Bitset);
+impl_set_operators!(impl [const WORDS: usize] FixedBitset<WORDS>);
+
+/// Flips all 64 bits; use [`Bitset::complement`] for a smaller universe.
+impl ops::Not for Bitset {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Bitset {
+            bitset: !self.bitset,
+        }
+    }
+}
+
+/// Flips all `WORDS * 64` bits; use [`FixedBitset::complement`] for a smaller universe.
+impl<const WORDS: usize> ops::Not for FixedBitset<WORDS> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.complement(Self::BITS)
+    }
+}
+
+// DynBitset has no upper bound, so it does not implement `Not`. Like `BTreeSet`, the binary
+// operators work on references to avoid cloning both operands.
+
+impl ops::BitAnd<&DynBitset> for &DynBitset {
+    type Output = DynBitset;
+
+    fn bitand(self, rhs: &DynBitset) -> Self::Output {
+        self.intersect(rhs)
+    }
+}
+
+impl ops::BitAndAssign<&DynBitset> for DynBitset {
+    fn bitand_assign(&mut self, rhs: &DynBitset) {
+        self.words.truncate(rhs.words.len());
+        for (w, r) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *w &= r;
+        }
+        self.trim();
+    }
+}
+
+impl ops::BitOr<&DynBitset> for &DynBitset {
+    type Output = DynBitset;
+
+    fn bitor(self, rhs: &DynBitset) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl ops::BitOrAssign<&DynBitset> for DynBitset {
+    fn bitor_assign(&mut self, rhs: &DynBitset) {
+        if self.words.len() < rhs.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (w, r) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *w |= r;
+        }
+    }
+}
+
+impl ops::BitXor<&DynBitset> for &DynBitset {
+    type Output = DynBitset;
+
+    fn bitxor(self, rhs: &DynBitset) -> Self::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl ops::BitXorAssign<&DynBitset> for DynBitset {
+    fn bitxor_assign(&mut self, rhs: &DynBitset) {
+        if self.words.len() < rhs.words.len() {
+            self.words.resize(rhs.words.len(), 0);
+        }
+        for (w, r) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *w ^= r;
+        }
+        self.trim();
+    }
+}
+
+impl ops::Sub<&DynBitset> for &DynBitset {
+    type Output = DynBitset;
+
+    fn sub(self, rhs: &DynBitset) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl ops::SubAssign<&DynBitset> for DynBitset {
+    fn sub_assign(&mut self, rhs: &DynBitset) {
+        for (w, r) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *w &= !r;
+        }
+        self.trim();
+    }
+}
+
+impl FromIterator<usize> for DynBitset {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = DynBitset::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl Extend<usize> for DynBitset {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for k in iter {
+            self.set(k);
+        }
+    }
+}
+
+/// Split position `k` into a word index and the mask selecting the bit within that word.
+fn locate(k: usize) -> (usize, u64) {
+    (k / WORD_BITS, 1 << (k % WORD_BITS))
+}
+
+/// The bits of word `i` that lie within the universe `0..n`.
+pub(crate) fn universe_word(i: usize, n: usize) -> u64 {
+    let start = i * WORD_BITS;
+    if n >= start + WORD_BITS {
+        u64::MAX
+    } else if n <= start {
+        0
+    } else {
+        (1 << (n - start)) - 1
+    }
+}
+
+/// Test if `a` is a subset of `b`, treating missing words as zero.
+fn words_is_subset(a: &[u64], b: &[u64]) -> bool {
+    a.iter()
+        .enumerate()
+        .all(|(i, &w)| w & !b.get(i).copied().unwrap_or(0) == 0)
+}
+
+fn words_is_disjoint(a: &[u64], b: &[u64]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x & y == 0)
+}
+
+fn words_count(words: &[u64]) -> usize {
+    words.iter().map(|w| w.count_ones() as usize).sum()
+}
+
+fn words_first(words: &[u64]) -> Option<usize> {
+    let i = words.iter().position(|&w| w != 0)?;
+    Some(i * WORD_BITS + words[i].trailing_zeros() as usize)
+}
+
+fn words_last(words: &[u64]) -> Option<usize> {
+    let i = words.iter().rposition(|&w| w != 0)?;
+    Some((i + 1) * WORD_BITS - 1 - words[i].leading_zeros() as usize)
+}
+
+/// Scatter the low bits of `src` onto the set positions of `mask`, lowest first.
+fn deposit(mut src: u64, mut mask: u64) -> u64 {
+    let mut result = 0;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if src & 1 != 0 {
+            result |= lowest;
+        }
+        src >>= 1;
+        mask ^= lowest;
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,11 +852,11 @@ mod tests {
     #[test]
     fn test_basic_operations() {
         let mut bitset = Bitset::new();
-        assert_eq!(false, bitset.is_set(1));
+        assert!(!bitset.is_set(1));
         bitset.set(1);
-        assert_eq!(true, bitset.is_set(1));
+        assert!(bitset.is_set(1));
         bitset.clear(1);
-        assert_eq!(false, bitset.is_set(1));
+        assert!(!bitset.is_set(1));
     }
 
     #[test]
@@ -125,4 +897,202 @@ mod tests {
         expected.set(3);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_dyn_basic_operations() {
+        let mut bitset = DynBitset::new();
+        assert!(!bitset.is_set(200));
+        bitset.set(200);
+        assert!(bitset.is_set(200));
+        bitset.toggle(3);
+        assert!(bitset.is_set(3));
+        bitset.clear(200);
+        assert!(!bitset.is_set(200));
+        bitset.toggle(3);
+        assert_eq!(DynBitset::new(), bitset);
+    }
+
+    #[test]
+    fn test_dyn_bits() {
+        let mut bitset = DynBitset::new();
+        for k in [1, 63, 64, 130] {
+            bitset.set(k);
+        }
+        let indices: Vec<usize> = bitset.iter().collect();
+        assert_eq!(vec![1, 63, 64, 130], indices);
+    }
+
+    #[test]
+    fn test_dyn_intersect_union() {
+        let mut b1 = DynBitset::new();
+        b1.set(1);
+        b1.set(100);
+        let mut b2 = DynBitset::new();
+        b2.set(1);
+        b2.set(200);
+
+        let mut expected = DynBitset::new();
+        expected.set(1);
+        assert_eq!(expected, b1.intersect(&b2));
+
+        expected.set(100);
+        expected.set(200);
+        assert_eq!(expected, b1.union(&b2));
+        assert_eq!(expected, b2.union(&b1));
+    }
+
+    #[test]
+    fn test_fixed_basic_operations() {
+        let mut bitset: FixedBitset<2> = FixedBitset::new();
+        assert_eq!(128, FixedBitset::<2>::BITS);
+        assert!(!bitset.is_set(127));
+        bitset.set(127);
+        assert!(bitset.is_set(127));
+        bitset.toggle(5);
+        assert!(bitset.is_set(5));
+        bitset.clear(127);
+        bitset.toggle(5);
+        assert_eq!(FixedBitset::default(), bitset);
+    }
+
+    #[test]
+    fn test_fixed_bits() {
+        let mut bitset: FixedBitset<4> = FixedBitset::new();
+        for k in [0, 63, 64, 255] {
+            bitset.set(k);
+        }
+        let indices: Vec<usize> = bitset.iter().collect();
+        assert_eq!(vec![0, 63, 64, 255], indices);
+    }
+
+    #[test]
+    fn test_fixed_intersect_union() {
+        let mut b1: FixedBitset<2> = FixedBitset::new();
+        b1.set(1);
+        b1.set(100);
+        let mut b2: FixedBitset<2> = FixedBitset::new();
+        b2.set(100);
+        b2.set(120);
+
+        let mut expected = FixedBitset::new();
+        expected.set(100);
+        assert_eq!(expected, b1.intersect(b2));
+
+        expected.set(1);
+        expected.set(120);
+        assert_eq!(expected, b1.union(b2));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let b1: Bitset = [1, 2, 3].into_iter().collect();
+        let b2: Bitset = [3, 4].into_iter().collect();
+        assert_eq!(Bitset::from_iter([1, 2]), b1 - b2);
+        assert_eq!(Bitset::from_iter([1, 2, 4]), b1 ^ b2);
+        assert_eq!(Bitset::from_iter([3]), b1 & b2);
+        assert_eq!(Bitset::from_iter([1, 2, 3, 4]), b1 | b2);
+        assert_eq!(Bitset::from_iter([0, 4]), b1.complement(5));
+        assert_eq!(61, (!b1).count());
+        assert!(Bitset::from_iter([2, 3]).is_subset(b1));
+        assert!(b1.is_superset(Bitset::from_iter([2, 3])));
+        assert!(!b1.is_disjoint(b2));
+        assert!(b2.is_disjoint(Bitset::from_iter([0, 63])));
+        assert_eq!(Some(1), b1.first());
+        assert_eq!(Some(3), b1.last());
+        assert_eq!(None, Bitset::new().first());
+        assert!(Bitset::new().is_empty());
+    }
+
+    #[test]
+    fn test_fixed_set_algebra() {
+        let b1: FixedBitset<2> = [1, 64, 127].into_iter().collect();
+        let b2: FixedBitset<2> = [64, 100].into_iter().collect();
+        assert_eq!(FixedBitset::from_iter([1, 127]), b1 - b2);
+        assert_eq!(FixedBitset::from_iter([1, 100, 127]), b1 ^ b2);
+        assert_eq!(FixedBitset::from_iter([64]), b1 & b2);
+        let complement = b1.complement(66);
+        assert_eq!(64, complement.count());
+        assert!(complement.is_set(65) && !complement.is_set(64));
+        assert_eq!(125, (!b1).count());
+        assert!(FixedBitset::from_iter([64]).is_subset(b1));
+        assert!(!b1.is_disjoint(b2));
+        assert_eq!(Some(1), b1.first());
+        assert_eq!(Some(127), b1.last());
+    }
+
+    #[test]
+    fn test_dyn_set_algebra() {
+        let b1: DynBitset = [1, 64, 200].into_iter().collect();
+        let b2: DynBitset = [64, 100].into_iter().collect();
+        assert_eq!(DynBitset::from_iter([1, 200]), &b1 - &b2);
+        assert_eq!(DynBitset::from_iter([1, 100, 200]), &b1 ^ &b2);
+        assert_eq!(DynBitset::from_iter([64]), &b1 & &b2);
+        assert_eq!(DynBitset::from_iter([0, 2, 3]), b1.complement(4));
+        assert!(DynBitset::from_iter([64]).is_subset(&b1));
+        assert!(!b2.is_subset(&b1));
+        assert!(!b1.is_disjoint(&b2));
+        assert_eq!(3, b1.count());
+        assert_eq!(Some(1), b1.first());
+        assert_eq!(Some(200), b1.last());
+
+        let mut b3 = b1.clone();
+        b3 -= &b1;
+        assert!(b3.is_empty());
+        b3 |= &b2;
+        b3 &= &b1;
+        assert_eq!(DynBitset::from_iter([64]), b3);
+    }
+
+    #[test]
+    fn test_subsets() {
+        let bitset = Bitset::from_iter([0, 2]);
+        let subsets: Vec<Bitset> = bitset.subsets().collect();
+        assert_eq!(
+            vec![
+                Bitset::from_iter([0, 2]),
+                Bitset::from_iter([2]),
+                Bitset::from_iter([0]),
+                Bitset::new(),
+            ],
+            subsets
+        );
+        assert_eq!(1 << 10, Bitset::new().complement(10).subsets().count());
+    }
+
+    #[test]
+    fn test_supersets() {
+        let bitset = Bitset::from_iter([1]);
+        let supersets: Vec<Bitset> = bitset.supersets(3).collect();
+        assert_eq!(
+            vec![
+                Bitset::from_iter([0, 1, 2]),
+                Bitset::from_iter([1, 2]),
+                Bitset::from_iter([0, 1]),
+                Bitset::from_iter([1]),
+            ],
+            supersets
+        );
+    }
+
+    #[test]
+    fn test_combinations() {
+        let bitset = Bitset::from_iter([1, 3, 4]);
+        let combinations: Vec<Bitset> = bitset.combinations(2).collect();
+        assert_eq!(
+            vec![
+                Bitset::from_iter([1, 3]),
+                Bitset::from_iter([1, 4]),
+                Bitset::from_iter([3, 4]),
+            ],
+            combinations
+        );
+        assert_eq!(
+            vec![Bitset::new()],
+            Bitset::new().combinations(0).collect::<Vec<_>>()
+        );
+        assert_eq!(0, bitset.combinations(4).count());
+        assert_eq!(0, bitset.combinations(200).count());
+        assert_eq!(2016, (!Bitset::new()).combinations(2).count());
+        assert_eq!(1, (!Bitset::new()).combinations(64).count());
+    }
 }
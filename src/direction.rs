@@ -0,0 +1,199 @@
+use num_integer::Integer;
+use num_traits::Signed;
+
+use crate::point::Point2D;
+
+/// One of the 4 orthogonal headings. As in `grid`, `y` grows downwards, so north is `(0, -1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A change of heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    Straight,
+    Back,
+}
+
+impl Direction {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Parse a direction from any of `U/D/L/R`, `N/E/S/W` or `^v<>`.
+    pub fn from_byte(b: u8) -> Option<Direction> {
+        match b {
+            b'U' | b'N' | b'^' => Some(Direction::North),
+            b'R' | b'E' | b'>' => Some(Direction::East),
+            b'D' | b'S' | b'v' => Some(Direction::South),
+            b'L' | b'W' | b'<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Rotate by 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Rotate by 90 degrees clockwise.
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn turn(self, turn: Turn) -> Direction {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+            Turn::Straight => self,
+            Turn::Back => self.reverse(),
+        }
+    }
+
+    /// The unit step in this direction.
+    pub fn offset<T>(self) -> Point2D<T>
+    where
+        T: Integer + Signed + Copy,
+    {
+        Direction8::from(self).offset()
+    }
+}
+
+impl Turn {
+    /// Parse a turn from `L` or `R`.
+    pub fn from_byte(b: u8) -> Option<Turn> {
+        match b {
+            b'L' => Some(Turn::Left),
+            b'R' => Some(Turn::Right),
+            _ => None,
+        }
+    }
+}
+
+/// One of the 8 headings including diagonals, `y` growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Rotate by 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Rotate by 45 degrees clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The unit step in this direction.
+    pub fn offset<T>(self) -> Point2D<T>
+    where
+        T: Integer + Signed + Copy,
+    {
+        let (zero, one) = (T::zero(), T::one());
+        let (x, y) = match self {
+            Direction8::North => (zero, -one),
+            Direction8::NorthEast => (one, -one),
+            Direction8::East => (one, zero),
+            Direction8::SouthEast => (one, one),
+            Direction8::South => (zero, one),
+            Direction8::SouthWest => (-one, one),
+            Direction8::West => (-one, zero),
+            Direction8::NorthWest => (-one, -one),
+        };
+        Point2D::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::West, Direction::North.turn_left());
+        assert_eq!(Direction::East, Direction::North.turn_right());
+        assert_eq!(Direction::North, Direction::South.reverse());
+        assert_eq!(Direction::West, Direction::East.turn(Turn::Back));
+        assert_eq!(Direction8::NorthWest, Direction8::North.turn_left());
+        assert_eq!(Direction8::SouthWest, Direction8::NorthEast.reverse());
+        assert_eq!(Direction8::South, Direction::South.into());
+    }
+
+    #[test]
+    fn test_from_byte() {
+        let dirs: Vec<Direction> = b"U>vW"
+            .iter()
+            .filter_map(|&b| Direction::from_byte(b))
+            .collect();
+        assert_eq!(
+            vec![
+                Direction::North,
+                Direction::East,
+                Direction::South,
+                Direction::West
+            ],
+            dirs
+        );
+        assert_eq!(None, Direction::from_byte(b'x'));
+        assert_eq!(Some(Turn::Left), Turn::from_byte(b'L'));
+    }
+
+    #[test]
+    fn test_step() {
+        let p: Point2D = Point2D::new(1, 1);
+        assert_eq!(Point2D::new(1, -2), p.step(Direction::North, 3));
+        assert_eq!(Point2D::new(3, 3), p.step(Direction8::SouthEast, 2));
+        assert_eq!(
+            Point2D::new(-1, 0),
+            Direction::West.offset::<i32>() + Point2D::new(0, 0)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::search;
+
 #[derive(Debug)]
 pub struct AdjacencyMatrix<const N: usize> {
     weights: [[i32; N]; N],
@@ -23,6 +25,22 @@ impl<const N: usize> AdjacencyMatrix<N> {
         self.weights[to][from] = weight;
     }
 
+    /// Iterate over the targets and weights of the edges leaving `node`.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, i32)> + '_ {
+        neighbors_in_row(&self.weights[node])
+    }
+
+    /// Shortest distances from `source` to every node using Dijkstra's algorithm, `None` for
+    /// unreachable nodes. Weights must not be negative.
+    pub fn dijkstra(&self, source: usize) -> Vec<Option<i32>> {
+        distances(N, source, |&n| self.neighbors(n))
+    }
+
+    /// A shortest path from `from` to `to` and its length. Weights must not be negative.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(Vec<usize>, i32)> {
+        search::dijkstra(from, |&n| self.neighbors(n), |&n| n == to)
+    }
+
     /// The Floyd Warshall Algorithm is for solving all pairs of shortest-path problems. The problem
     /// is to find the shortest distances between every pair of vertices in a given edge-weighted
     /// directed graph.
@@ -63,6 +81,249 @@ impl<const N: usize> Default for AdjacencyMatrix<N> {
     }
 }
 
+/// A dense graph like `AdjacencyMatrix`, but heap-allocated and sized at runtime.
+#[derive(Debug, Clone)]
+pub struct DynAdjacencyMatrix {
+    n: usize,
+    // Row-major `n * n` matrix.
+    weights: Vec<i32>,
+}
+
+impl DynAdjacencyMatrix {
+    /// Create a graph with `n` nodes and no edges.
+    pub fn new(n: usize) -> Self {
+        DynAdjacencyMatrix {
+            n,
+            weights: vec![INF; n * n],
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.n
+    }
+
+    /// Position of the edge from `from` to `to` in `weights`.
+    fn idx(&self, from: usize, to: usize) -> usize {
+        assert!(from < self.n && to < self.n, "node out of bounds");
+        from * self.n + to
+    }
+
+    /// Weight of the edge from `from` to `to`, `None` if there is no such edge.
+    pub fn weight(&self, from: usize, to: usize) -> Option<i32> {
+        let w = self.weights[self.idx(from, to)];
+        (w != INF).then_some(w)
+    }
+
+    /// Add a directed edge.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: i32) {
+        let i = self.idx(from, to);
+        self.weights[i] = weight;
+    }
+
+    /// Add an undirected edge.
+    pub fn add_edge_undirected(&mut self, from: usize, to: usize, weight: i32) {
+        self.add_edge(from, to, weight);
+        self.add_edge(to, from, weight);
+    }
+
+    /// Iterate over the targets and weights of the edges leaving `node`.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, i32)> + '_ {
+        let start = self.idx(node, 0);
+        neighbors_in_row(&self.weights[start..start + self.n])
+    }
+
+    /// Shortest distances from `source` to every node using Dijkstra's algorithm, `None` for
+    /// unreachable nodes. Weights must not be negative.
+    pub fn dijkstra(&self, source: usize) -> Vec<Option<i32>> {
+        distances(self.n, source, |&n| self.neighbors(n))
+    }
+
+    /// A shortest path from `from` to `to` and its length. Weights must not be negative.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(Vec<usize>, i32)> {
+        search::dijkstra(from, |&n| self.neighbors(n), |&n| n == to)
+    }
+
+    /// All pairs shortest paths, see [`AdjacencyMatrix::floyd_warshall`].
+    /// Complexity: O(n^3)
+    pub fn floyd_warshall(&self) -> Vec<Vec<i32>> {
+        let n = self.n;
+        let mut dist = self.weights.clone();
+        for k in 0..n {
+            for i in 0..n {
+                let d_ik = dist[i * n + k];
+                if d_ik == INF {
+                    continue;
+                }
+                for j in 0..n {
+                    let d_kj = dist[k * n + j];
+                    if d_kj != INF && dist[i * n + j] > d_ik + d_kj {
+                        dist[i * n + j] = d_ik + d_kj;
+                    }
+                }
+            }
+        }
+        dist.chunks(n.max(1)).map(|row| row.to_vec()).collect()
+    }
+}
+
+impl<const N: usize> From<&AdjacencyMatrix<N>> for DynAdjacencyMatrix {
+    fn from(matrix: &AdjacencyMatrix<N>) -> Self {
+        DynAdjacencyMatrix {
+            n: N,
+            weights: matrix.weights.iter().flatten().copied().collect(),
+        }
+    }
+}
+
+/// Index of a node in an `AdjacencyList`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn new(index: usize) -> Self {
+        NodeId(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for NodeId {
+    fn from(index: usize) -> Self {
+        NodeId(index)
+    }
+}
+
+/// A sparse graph whose size is only known at runtime. Both outgoing and incoming edges are
+/// stored, so neighbors can be iterated in either direction.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyList {
+    out_edges: Vec<Vec<(NodeId, i32)>>,
+    in_edges: Vec<Vec<(NodeId, i32)>>,
+}
+
+impl AdjacencyList {
+    pub fn new() -> Self {
+        AdjacencyList {
+            out_edges: Vec::new(),
+            in_edges: Vec::new(),
+        }
+    }
+
+    /// Create a graph with `n` nodes and no edges.
+    pub fn with_nodes(n: usize) -> Self {
+        AdjacencyList {
+            out_edges: vec![Vec::new(); n],
+            in_edges: vec![Vec::new(); n],
+        }
+    }
+
+    /// Add a node without edges.
+    pub fn add_node(&mut self) -> NodeId {
+        self.out_edges.push(Vec::new());
+        self.in_edges.push(Vec::new());
+        NodeId(self.out_edges.len() - 1)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.out_edges.len()
+    }
+
+    /// Number of directed edges; an undirected edge counts twice.
+    pub fn edge_count(&self) -> usize {
+        self.out_edges.iter().map(|edges| edges.len()).sum()
+    }
+
+    /// Iterate over all nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.node_count()).map(NodeId)
+    }
+
+    /// Add a directed edge.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: i32) {
+        self.out_edges[from.0].push((to, weight));
+        self.in_edges[to.0].push((from, weight));
+    }
+
+    /// Add an undirected edge.
+    pub fn add_edge_undirected(&mut self, from: NodeId, to: NodeId, weight: i32) {
+        self.add_edge(from, to, weight);
+        self.add_edge(to, from, weight);
+    }
+
+    /// Iterate over the targets and weights of the edges leaving `node`.
+    pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = (NodeId, i32)> + '_ {
+        self.out_edges[node.0].iter().copied()
+    }
+
+    /// Iterate over the sources and weights of the edges entering `node`.
+    pub fn in_neighbors(&self, node: NodeId) -> impl Iterator<Item = (NodeId, i32)> + '_ {
+        self.in_edges[node.0].iter().copied()
+    }
+
+    /// Shortest distances from `source` to every node using Dijkstra's algorithm, `None` for
+    /// unreachable nodes. Weights must not be negative.
+    pub fn dijkstra(&self, source: NodeId) -> Vec<Option<i32>> {
+        let dist = search::dijkstra_all(source, |&n| self.neighbors(n));
+        self.nodes()
+            .map(|n| dist.get(&n).map(|&(cost, _)| cost))
+            .collect()
+    }
+
+    /// A shortest path from `from` to `to` and its length. Weights must not be negative.
+    pub fn shortest_path(&self, from: NodeId, to: NodeId) -> Option<(Vec<NodeId>, i32)> {
+        search::dijkstra(from, |&n| self.neighbors(n), |&n| n == to)
+    }
+
+    /// Convert to a dense matrix. Parallel edges keep the smallest weight.
+    /// Panics if the graph has more than `N` nodes.
+    pub fn to_matrix<const N: usize>(&self) -> AdjacencyMatrix<N> {
+        assert!(self.node_count() <= N, "graph has more than {} nodes", N);
+        let mut matrix = AdjacencyMatrix::new();
+        for from in self.nodes() {
+            for (to, weight) in self.neighbors(from) {
+                let entry = &mut matrix.weights[from.0][to.0];
+                *entry = (*entry).min(weight);
+            }
+        }
+        matrix
+    }
+}
+
+impl<const N: usize> From<&AdjacencyMatrix<N>> for AdjacencyList {
+    fn from(matrix: &AdjacencyMatrix<N>) -> Self {
+        let mut graph = AdjacencyList::with_nodes(N);
+        for (from, row) in matrix.weights.iter().enumerate() {
+            for (to, &weight) in row.iter().enumerate() {
+                if weight != INF {
+                    graph.add_edge(NodeId(from), NodeId(to), weight);
+                }
+            }
+        }
+        graph
+    }
+}
+
+fn neighbors_in_row(row: &[i32]) -> impl Iterator<Item = (usize, i32)> + '_ {
+    row.iter()
+        .enumerate()
+        .filter(|(_, &w)| w != INF)
+        .map(|(to, &w)| (to, w))
+}
+
+/// Dijkstra distances on nodes `0..n`.
+fn distances<FN, IN>(n: usize, source: usize, successors: FN) -> Vec<Option<i32>>
+where
+    FN: FnMut(&usize) -> IN,
+    IN: IntoIterator<Item = (usize, i32)>,
+{
+    let dist = search::dijkstra_all(source, successors);
+    (0..n)
+        .map(|i| dist.get(&i).map(|&(cost, _)| cost))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +358,121 @@ mod tests {
             dist
         );
     }
+
+    #[test]
+    fn test_adjacency_list() {
+        let mut g = AdjacencyList::new();
+        let a = g.add_node();
+        let b = g.add_node();
+        let c = g.add_node();
+        g.add_edge(a, b, 5);
+        g.add_edge_undirected(b, c, 3);
+        assert_eq!(3, g.node_count());
+        assert_eq!(3, g.edge_count());
+        assert_eq!(vec![(c, 3)], g.neighbors(b).collect::<Vec<_>>());
+        assert_eq!(vec![(a, 5), (c, 3)], g.in_neighbors(b).collect::<Vec<_>>());
+        assert_eq!(0, g.neighbors(a).filter(|&(n, _)| n == c).count());
+        assert_eq!(NodeId::new(2), NodeId::from(c.index()));
+    }
+
+    #[test]
+    fn test_adjacency_list_matrix_roundtrip() {
+        let mut g = AdjacencyList::with_nodes(4);
+        g.add_edge(0.into(), 3.into(), 10);
+        g.add_edge(0.into(), 1.into(), 5);
+        g.add_edge(1.into(), 2.into(), 3);
+        g.add_edge(2.into(), 3.into(), 1);
+        g.add_edge(2.into(), 3.into(), 7);
+        for i in 0..4 {
+            g.add_edge(i.into(), i.into(), 0);
+        }
+        let matrix: AdjacencyMatrix<4> = g.to_matrix();
+        assert_eq!([0, 5, 8, 9], matrix.floyd_warshall()[0]);
+
+        let back = AdjacencyList::from(&matrix);
+        assert_eq!(8, back.edge_count());
+        assert_eq!(
+            vec![(NodeId(3), 1)],
+            back.neighbors(NodeId(2))
+                .filter(|&(n, _)| n.0 == 3)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_dyn_floyd_warshall() {
+        let mut g = DynAdjacencyMatrix::new(4);
+        g.add_edge(0, 3, 10);
+        g.add_edge(0, 1, 5);
+        g.add_edge(1, 2, 3);
+        g.add_edge(2, 3, 1);
+        for i in 0..4 {
+            g.add_edge(i, i, 0);
+        }
+        assert_eq!(Some(5), g.weight(0, 1));
+        assert_eq!(None, g.weight(1, 0));
+        let dist = g.floyd_warshall();
+        assert_eq!(
+            vec![
+                vec![0, 5, 8, 9],
+                vec![INF, 0, 3, 4],
+                vec![INF, INF, 0, 1],
+                vec![INF, INF, INF, 0],
+            ],
+            dist
+        );
+
+        let mut fixed: AdjacencyMatrix<4> = AdjacencyMatrix::new();
+        fixed.add_edge_undirected(0, 1, 2);
+        let g = DynAdjacencyMatrix::from(&fixed);
+        assert_eq!(Some(2), g.weight(1, 0));
+        assert_eq!(
+            fixed.floyd_warshall().to_vec(),
+            g.floyd_warshall()
+                .into_iter()
+                .map(|row| <[i32; 4]>::try_from(row).unwrap())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_dyn_large() {
+        // Too large for a stack-allocated `AdjacencyMatrix`.
+        let n = 2000;
+        let mut g = DynAdjacencyMatrix::new(n);
+        g.add_edge_undirected(0, n - 1, 7);
+        assert_eq!(Some(7), g.weight(n - 1, 0));
+        assert_eq!(n, g.node_count());
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let mut fixed: AdjacencyMatrix<4> = AdjacencyMatrix::new();
+        fixed.add_edge(0, 3, 10);
+        fixed.add_edge(0, 1, 5);
+        fixed.add_edge(1, 2, 3);
+        fixed.add_edge(2, 3, 1);
+        assert_eq!(vec![Some(0), Some(5), Some(8), Some(9)], fixed.dijkstra(0));
+        assert_eq!(vec![None, Some(0), Some(3), Some(4)], fixed.dijkstra(1));
+        assert_eq!(Some((vec![0, 1, 2, 3], 9)), fixed.shortest_path(0, 3));
+        assert_eq!(None, fixed.shortest_path(3, 0));
+
+        let dynamic = DynAdjacencyMatrix::from(&fixed);
+        assert_eq!(fixed.dijkstra(0), dynamic.dijkstra(0));
+        assert_eq!(Some((vec![1, 2, 3], 4)), dynamic.shortest_path(1, 3));
+
+        let list = AdjacencyList::from(&fixed);
+        assert_eq!(fixed.dijkstra(0), list.dijkstra(NodeId(0)));
+        assert_eq!(
+            Some((vec![NodeId(0), NodeId(1), NodeId(2)], 8)),
+            list.shortest_path(NodeId(0), NodeId(2))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dyn_matrix_out_of_bounds() {
+        let mut graph = DynAdjacencyMatrix::new(4);
+        graph.add_edge(0, 4, 9);
+    }
 }
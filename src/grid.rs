@@ -0,0 +1,485 @@
+use core::ops;
+use std::collections::HashMap;
+
+use crate::point::Point2D;
+
+/// Offsets of the 4 orthogonal neighbors, clockwise starting north (`y` grows downwards).
+pub const NEIGHBORS4: [Point2D; 4] = [
+    Point2D { x: 0, y: -1 },
+    Point2D { x: 1, y: 0 },
+    Point2D { x: 0, y: 1 },
+    Point2D { x: -1, y: 0 },
+];
+
+/// Offsets of the 8 surrounding neighbors, clockwise starting north (`y` grows downwards).
+pub const NEIGHBORS8: [Point2D; 8] = [
+    Point2D { x: 0, y: -1 },
+    Point2D { x: 1, y: -1 },
+    Point2D { x: 1, y: 0 },
+    Point2D { x: 1, y: 1 },
+    Point2D { x: 0, y: 1 },
+    Point2D { x: -1, y: 1 },
+    Point2D { x: -1, y: 0 },
+    Point2D { x: -1, y: -1 },
+];
+
+/// Iterate over the 4 orthogonal neighbors of `p`, without any bounds.
+pub fn neighbors4(p: Point2D) -> impl Iterator<Item = Point2D> {
+    NEIGHBORS4.into_iter().map(move |d| p + d)
+}
+
+/// Iterate over the 8 surrounding neighbors of `p`, without any bounds.
+pub fn neighbors8(p: Point2D) -> impl Iterator<Item = Point2D> {
+    NEIGHBORS8.into_iter().map(move |d| p + d)
+}
+
+/// A dense, rectangular grid stored row by row. `(0, 0)` is the top-left cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "cells do not match dimensions");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Build a grid from puzzle input such as returned by `io::read_input`, mapping every byte
+    /// with `f`. Empty lines are skipped and all other lines must have the same length.
+    pub fn from_input<F>(input: &[u8], mut f: F) -> Self
+    where
+        F: FnMut(u8) -> T,
+    {
+        let mut width = 0;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.split(|&b| b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            if height == 0 {
+                width = line.len();
+            }
+            assert_eq!(width, line.len(), "line {} has a different width", height);
+            cells.extend(line.iter().map(|&b| f(b)));
+            height += 1;
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Test if `p` lies within the grid.
+    pub fn in_bounds(&self, p: Point2D) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    pub fn get(&self, p: Point2D) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point2D) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Row `y` as a slice.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "y out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterate over the rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// Iterate over the cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "x out of bounds");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// Iterate over the columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Iterate over all cells together with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (self.point(i), cell))
+    }
+
+    /// Find the position of the first cell, row by row, satisfying `predicate`.
+    pub fn position<P>(&self, predicate: P) -> Option<Point2D>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.iter().position(predicate).map(|i| self.point(i))
+    }
+
+    /// Iterate over the orthogonal neighbors of `p` that lie within the grid.
+    pub fn neighbors4(&self, p: Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        neighbors4(p).filter(move |&q| self.in_bounds(q))
+    }
+
+    /// Iterate over the surrounding neighbors of `p` that lie within the grid.
+    pub fn neighbors8(&self, p: Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        neighbors8(p).filter(move |&q| self.in_bounds(q))
+    }
+
+    /// Apply `f` to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset(&self, p: Point2D) -> Option<usize> {
+        if self.in_bounds(p) {
+            Some(p.y as usize * self.width + p.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn point(&self, i: usize) -> Point2D {
+        Point2D::new((i % self.width) as i64, (i / self.width) as i64)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a `width` x `height` grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirror along the main diagonal.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate by 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let h = self.height;
+        self.remap(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    /// Rotate by 90 degrees counterclockwise.
+    pub fn rotate_left(&self) -> Self {
+        let w = self.width;
+        self.remap(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.remap(self.width, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirror top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.remap(self.width, self.height, |x, y| (x, h - 1 - y))
+    }
+
+    /// Build a `width` x `height` grid where cell `(x, y)` is copied from `source(x, y)`.
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+        Grid::new(width, height, cells)
+    }
+}
+
+impl<T> ops::Index<Point2D> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2D) -> &Self::Output {
+        self.get(p).expect("point out of bounds")
+    }
+}
+
+impl<T> ops::IndexMut<Point2D> for Grid<T> {
+    fn index_mut(&mut self, p: Point2D) -> &mut Self::Output {
+        self.get_mut(p).expect("point out of bounds")
+    }
+}
+
+/// A sparse, unbounded grid that keeps track of the bounding box of its cells.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2D, T>,
+    bounds: Option<(Point2D, Point2D)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Insert `value` at `p`, returning the previous value.
+    pub fn insert(&mut self, p: Point2D, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point2D::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2D::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+            None => (p, p),
+        });
+        self.cells.insert(p, value)
+    }
+
+    /// Remove the value at `p`. Removing a cell on the border recomputes the bounding box.
+    pub fn remove(&mut self, p: Point2D) -> Option<T> {
+        let value = self.cells.remove(&p)?;
+        if let Some((min, max)) = self.bounds {
+            if p.x == min.x || p.y == min.y || p.x == max.x || p.y == max.y {
+                self.bounds = compute_bounds(self.cells.keys().copied());
+            }
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, p: Point2D) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2D) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point2D) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// Iterate over all cells in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2D, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    /// Top-left corner of the bounding box.
+    pub fn min(&self) -> Option<Point2D> {
+        self.bounds.map(|(min, _)| min)
+    }
+
+    /// Bottom-right corner of the bounding box.
+    pub fn max(&self) -> Option<Point2D> {
+        self.bounds.map(|(_, max)| max)
+    }
+
+    /// Iterate over the orthogonal neighbors of `p` that are present.
+    pub fn neighbors4(&self, p: Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        neighbors4(p).filter(move |&q| self.contains(q))
+    }
+
+    /// Iterate over the surrounding neighbors of `p` that are present.
+    pub fn neighbors8(&self, p: Point2D) -> impl Iterator<Item = Point2D> + '_ {
+        neighbors8(p).filter(move |&q| self.contains(q))
+    }
+
+    /// Render the bounding box row by row, mapping every position with `f`.
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(Option<&T>) -> char,
+    {
+        let mut s = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    s.push(f(self.get(Point2D::new(x, y))));
+                }
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Collect the cells of `grid` for which `keep` returns true.
+    pub fn from_grid<F>(grid: &Grid<T>, keep: F) -> Self
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut sparse = SparseGrid::new();
+        for (p, v) in grid.iter() {
+            if keep(v) {
+                sparse.insert(p, v.clone());
+            }
+        }
+        sparse
+    }
+
+    /// Convert the bounding box to a dense grid, filling missing cells with `empty`.
+    /// Cell `(0, 0)` of the result corresponds to [`SparseGrid::min`].
+    pub fn to_grid(&self, empty: T) -> Grid<T> {
+        let Some((min, max)) = self.bounds else {
+            return Grid::new(0, 0, Vec::new());
+        };
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::filled(width, height, empty);
+        for (&p, v) in self.cells.iter() {
+            grid[p - min] = v.clone();
+        }
+        grid
+    }
+}
+
+fn compute_bounds<I>(mut points: I) -> Option<(Point2D, Point2D)>
+where
+    I: Iterator<Item = Point2D>,
+{
+    let first = points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            Point2D::new(min.x.min(p.x), min.y.min(p.y)),
+            Point2D::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<u8> {
+        Grid::from_input(b"abc\r\ndef\n", |b| b)
+    }
+
+    #[test]
+    fn test_from_input() {
+        let grid = example();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(b'f', grid[Point2D::new(2, 1)]);
+        assert_eq!(None, grid.get(Point2D::new(3, 0)));
+        assert_eq!(None, grid.get(Point2D::new(0, -1)));
+        assert_eq!(Some(Point2D::new(1, 1)), grid.position(|&b| b == b'e'));
+
+        let digits = Grid::from_input(b"12\n34\n\n", |b| b - b'0');
+        assert_eq!(
+            vec![&[1, 2][..], &[3, 4][..]],
+            digits.rows().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let grid = example();
+        let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(
+            vec![b"ad".to_vec(), b"be".to_vec(), b"cf".to_vec()],
+            columns
+        );
+    }
+
+    #[test]
+    fn test_zero_width_rows() {
+        let grid: Grid<u8> = Grid::new(0, 3, vec![]);
+        assert_eq!(3, grid.rows().count());
+        assert!(grid.rows().all(|row| row.is_empty()));
+        assert_eq!(0, grid.columns().count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_column_out_of_bounds() {
+        let _ = example().column(3);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = example();
+        let corner: Vec<Point2D> = grid.neighbors4(Point2D::new(0, 0)).collect();
+        assert_eq!(vec![Point2D::new(1, 0), Point2D::new(0, 1)], corner);
+        assert_eq!(5, grid.neighbors8(Point2D::new(1, 0)).count());
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = example();
+        assert_eq!(Grid::from_input(b"ad\nbe\ncf\n", |b| b), grid.transpose());
+        assert_eq!(
+            Grid::from_input(b"da\neb\nfc\n", |b| b),
+            grid.rotate_right()
+        );
+        assert_eq!(Grid::from_input(b"cf\nbe\nad\n", |b| b), grid.rotate_left());
+        assert_eq!(
+            Grid::from_input(b"cba\nfed\n", |b| b),
+            grid.flip_horizontal()
+        );
+        assert_eq!(Grid::from_input(b"def\nabc\n", |b| b), grid.flip_vertical());
+        assert_eq!(grid, grid.rotate_right().rotate_left());
+    }
+
+    #[test]
+    fn test_sparse_bounds() {
+        let mut sparse = SparseGrid::new();
+        assert_eq!(None, sparse.min());
+        sparse.insert(Point2D::new(2, -1), '#');
+        sparse.insert(Point2D::new(-3, 4), '#');
+        sparse.insert(Point2D::new(0, 0), 'o');
+        assert_eq!(Some(Point2D::new(-3, -1)), sparse.min());
+        assert_eq!(Some(Point2D::new(2, 4)), sparse.max());
+
+        sparse.remove(Point2D::new(-3, 4));
+        assert_eq!(Some(Point2D::new(0, -1)), sparse.min());
+        assert_eq!(Some(Point2D::new(2, 0)), sparse.max());
+        assert_eq!("..#\no..\n", sparse.render(|c| c.copied().unwrap_or('.')));
+        assert_eq!(0, sparse.neighbors8(Point2D::new(0, 0)).count());
+    }
+
+    #[test]
+    fn test_sparse_dense_roundtrip() {
+        let grid = Grid::from_input(b"..#\n#..\n", |b| b);
+        let sparse = SparseGrid::from_grid(&grid, |&b| b == b'#');
+        assert_eq!(2, sparse.len());
+        assert_eq!(Some(Point2D::new(0, 0)), sparse.min());
+        assert_eq!(grid, sparse.to_grid(b'.'));
+    }
+}
@@ -1,20 +1,47 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-/// Represents the interval [a, b].
-pub struct ClosedInterval {
-    pub a: i64,
-    pub b: i64,
+use core::ops;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num_integer::Integer;
+use num_traits::{PrimInt, Signed};
+
+use crate::grid::Grid;
+use crate::point::Point2D;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the interval [a, b]. The interval is empty if `a > b`.
+///
+/// `T` is bounded by `PrimInt` instead of the `Integer + Signed` bound of `Point2D` so that
+/// unsigned types like `usize` work too. Every signed primitive satisfies both, so types mixing
+/// points and intervals, like `CompressedGrid`, use `PrimInt + Integer + Signed`.
+pub struct ClosedInterval<T = i64>
+where
+    T: PrimInt,
+{
+    pub a: T,
+    pub b: T,
 }
 
-impl ClosedInterval {
-    pub fn new(a: i64, b: i64) -> ClosedInterval {
-        debug_assert!(a <= b);
+impl<T> ClosedInterval<T>
+where
+    T: PrimInt,
+{
+    pub fn new(a: T, b: T) -> ClosedInterval<T> {
         ClosedInterval { a, b }
     }
 
+    /// The canonical empty interval [1, 0].
+    pub fn empty() -> ClosedInterval<T> {
+        ClosedInterval::new(T::one(), T::zero())
+    }
+
     /// Count (whole) points in the interval.
     /// Example: [1, 3] contains 3 points.
     pub fn len(&self) -> usize {
-        (self.b - self.a) as usize + 1
+        if self.is_empty() {
+            return 0;
+        }
+        span(self.a, self.b) + 1
     }
 
     pub fn is_empty(&self) -> bool {
@@ -22,12 +49,27 @@ impl ClosedInterval {
     }
 
     /// Test if `interval` is a subset of `self`.
-    pub fn contains(&self, interval: &ClosedInterval) -> bool {
-        self.a <= interval.a && interval.b <= self.b
+    pub fn contains(&self, interval: &ClosedInterval<T>) -> bool {
+        interval.is_empty() || (self.a <= interval.a && interval.b <= self.b)
+    }
+
+    /// Test if `x` lies in the interval.
+    pub fn contains_point(&self, x: T) -> bool {
+        self.a <= x && x <= self.b
+    }
+
+    /// Iterate over the points in the interval, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let b = self.b;
+        let first = (!self.is_empty()).then_some(self.a);
+        std::iter::successors(first, move |&x| (x < b).then(|| x + T::one()))
     }
 
     /// Test if the intervals are disjoint.
-    pub fn disjoint(&self, interval: &ClosedInterval) -> bool {
+    pub fn disjoint(&self, interval: &ClosedInterval<T>) -> bool {
+        if self.is_empty() || interval.is_empty() {
+            return true;
+        }
         let (lhs, rhs) = if self <= interval {
             (self, interval)
         } else {
@@ -37,7 +79,7 @@ impl ClosedInterval {
     }
 
     /// Intersect with interval.
-    pub fn intersect(&self, interval: &ClosedInterval) -> Option<ClosedInterval> {
+    pub fn intersect(&self, interval: &ClosedInterval<T>) -> Option<ClosedInterval<T>> {
         let a = std::cmp::max(self.a, interval.a);
         let b = std::cmp::min(self.b, interval.b);
         if a <= b {
@@ -47,24 +89,147 @@ impl ClosedInterval {
     }
 }
 
+impl<T> From<ops::RangeInclusive<T>> for ClosedInterval<T>
+where
+    T: PrimInt,
+{
+    fn from(range: ops::RangeInclusive<T>) -> Self {
+        let (a, b) = range.into_inner();
+        ClosedInterval::new(a, b)
+    }
+}
+
+impl<T> From<HalfOpenInterval<T>> for ClosedInterval<T>
+where
+    T: PrimInt,
+{
+    fn from(interval: HalfOpenInterval<T>) -> Self {
+        if interval.is_empty() {
+            return ClosedInterval::empty();
+        }
+        ClosedInterval::new(interval.a, interval.b - T::one())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Represents the interval [a, b). The interval is empty if `a >= b`.
+pub struct HalfOpenInterval<T = i64>
+where
+    T: PrimInt,
+{
+    pub a: T,
+    pub b: T,
+}
+
+impl<T> HalfOpenInterval<T>
+where
+    T: PrimInt,
+{
+    pub fn new(a: T, b: T) -> HalfOpenInterval<T> {
+        HalfOpenInterval { a, b }
+    }
+
+    /// The canonical empty interval [0, 0).
+    pub fn empty() -> HalfOpenInterval<T> {
+        HalfOpenInterval::new(T::zero(), T::zero())
+    }
+
+    /// Count (whole) points in the interval.
+    /// Example: [1, 3) contains 2 points.
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        span(self.a, self.b)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a >= self.b
+    }
+
+    /// Test if `interval` is a subset of `self`.
+    pub fn contains(&self, interval: &HalfOpenInterval<T>) -> bool {
+        interval.is_empty() || (self.a <= interval.a && interval.b <= self.b)
+    }
+
+    /// Test if the intervals are disjoint.
+    pub fn disjoint(&self, interval: &HalfOpenInterval<T>) -> bool {
+        self.intersect(interval).is_none()
+    }
+
+    /// Intersect with interval.
+    pub fn intersect(&self, interval: &HalfOpenInterval<T>) -> Option<HalfOpenInterval<T>> {
+        let a = std::cmp::max(self.a, interval.a);
+        let b = std::cmp::min(self.b, interval.b);
+        if a < b {
+            return Some(HalfOpenInterval::new(a, b));
+        }
+        None
+    }
+}
+
+impl<T> From<ops::Range<T>> for HalfOpenInterval<T>
+where
+    T: PrimInt,
+{
+    fn from(range: ops::Range<T>) -> Self {
+        HalfOpenInterval::new(range.start, range.end)
+    }
+}
+
+impl<T> From<ClosedInterval<T>> for HalfOpenInterval<T>
+where
+    T: PrimInt,
+{
+    /// Panics if `interval` is not empty and `interval.b` is the largest value of `T`, since
+    /// the end point is then not representable.
+    fn from(interval: ClosedInterval<T>) -> Self {
+        if interval.is_empty() {
+            return HalfOpenInterval::empty();
+        }
+        let b = interval
+            .b
+            .checked_add(&T::one())
+            .expect("end point not representable");
+        HalfOpenInterval::new(interval.a, b)
+    }
+}
+
+/// Compute `b - a` for `a <= b`. The difference may not fit into `T` for signed types.
+fn span<T: PrimInt>(a: T, b: T) -> usize {
+    let diff = match b.checked_sub(&a) {
+        Some(diff) => diff.to_usize(),
+        // Only signed types overflow here, and those fit into i128. The difference of two i128
+        // values always fits into u128.
+        None => {
+            let diff = b.to_i128().unwrap().wrapping_sub(a.to_i128().unwrap()) as u128;
+            usize::try_from(diff).ok()
+        }
+    };
+    diff.expect("interval too long")
+}
+
 /// Given an array of intervals where intervals[i] = [starti, endi], merge all overlapping
 /// intervals, and return an array of the non-overlapping intervals that cover all the intervals in
-/// the input.
+/// the input. Empty intervals are ignored.
 ///
 /// Example:
 ///
 /// Input: `[[1,3],[2,6],[8,10],[15,18]]`
 /// Output: `[[1,6],[8,10],[15,18]]`
-pub fn merge_intervals(intervals: &[ClosedInterval]) -> Vec<ClosedInterval> {
+pub fn merge_intervals<T>(intervals: &[ClosedInterval<T>]) -> Vec<ClosedInterval<T>>
+where
+    T: PrimInt,
+{
     // see https://leetcode.com/problems/merge-intervals/description/
     // based on https://leetcode.com/problems/merge-intervals/solutions/21223/beat-98-java-sort-start-end-respectively/
-    let n = intervals.len();
-    let mut starts = Vec::with_capacity(n);
-    let mut ends = Vec::with_capacity(n);
-    for int in intervals {
+    let mut starts = Vec::with_capacity(intervals.len());
+    let mut ends = Vec::with_capacity(intervals.len());
+    for int in intervals.iter().filter(|int| !int.is_empty()) {
         starts.push(int.a);
         ends.push(int.b);
     }
+    let n = starts.len();
     starts.sort_unstable();
     ends.sort_unstable();
     let (starts, ends) = (starts, ends);
@@ -81,20 +246,892 @@ pub fn merge_intervals(intervals: &[ClosedInterval]) -> Vec<ClosedInterval> {
     res
 }
 
+/// A set of integers stored as sorted, disjoint and non-adjacent closed intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T = i64>
+where
+    T: PrimInt,
+{
+    intervals: Vec<ClosedInterval<T>>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// The normalized intervals, in increasing order.
+    pub fn intervals(&self) -> &[ClosedInterval<T>] {
+        &self.intervals
+    }
+
+    /// Iterate over the normalized intervals, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = &ClosedInterval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Count (whole) points in the set.
+    pub fn len(&self) -> usize {
+        self.intervals.iter().map(|int| int.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Test if `x` is in the set.
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|int| int.b < x);
+        self.intervals.get(i).is_some_and(|int| int.a <= x)
+    }
+
+    /// Add all points of `interval`, merging it with overlapping and adjacent intervals.
+    pub fn insert(&mut self, interval: ClosedInterval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Intervals ending before `interval.a - 1` and starting after `interval.b + 1` stay.
+        let lo = self
+            .intervals
+            .partition_point(|int| int.b < interval.a.saturating_sub(T::one()));
+        let hi = self
+            .intervals
+            .partition_point(|int| int.a <= interval.b.saturating_add(T::one()));
+        let mut merged = interval;
+        if lo < hi {
+            merged.a = merged.a.min(self.intervals[lo].a);
+            merged.b = merged.b.max(self.intervals[hi - 1].b);
+        }
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// Remove all points of `interval`, splitting intervals that contain it.
+    pub fn remove(&mut self, interval: ClosedInterval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let lo = self.intervals.partition_point(|int| int.b < interval.a);
+        let hi = self.intervals.partition_point(|int| int.a <= interval.b);
+        if lo == hi {
+            return;
+        }
+        let first = self.intervals[lo];
+        let last = self.intervals[hi - 1];
+        let below =
+            (first.a < interval.a).then(|| ClosedInterval::new(first.a, interval.a - T::one()));
+        let above =
+            (interval.b < last.b).then(|| ClosedInterval::new(interval.b + T::one(), last.b));
+        self.intervals
+            .splice(lo..hi, below.into_iter().chain(above));
+    }
+
+    /// Points in `self` or `other`.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for &int in other.iter() {
+            result.insert(int);
+        }
+        result
+    }
+
+    /// Points in both `self` and `other`.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (x, y) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(common) = x.intersect(y) {
+                intervals.push(common);
+            }
+            if x.b < y.b {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Points in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for &int in other.iter() {
+            result.remove(int);
+        }
+        result
+    }
+
+    /// Iterate over the maximal intervals between consecutive intervals of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = ClosedInterval<T>> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|w| ClosedInterval::new(w[0].b + T::one(), w[1].a - T::one()))
+    }
+}
+
+impl<T> FromIterator<ClosedInterval<T>> for IntervalSet<T>
+where
+    T: PrimInt,
+{
+    fn from_iter<I: IntoIterator<Item = ClosedInterval<T>>>(iter: I) -> Self {
+        let mut result = IntervalSet::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T> Extend<ClosedInterval<T>> for IntervalSet<T>
+where
+    T: PrimInt,
+{
+    fn extend<I: IntoIterator<Item = ClosedInterval<T>>>(&mut self, iter: I) {
+        for int in iter {
+            self.insert(int);
+        }
+    }
+}
+
+/// A piecewise shift of the integers: every point in one of the source intervals is moved by
+/// that interval's offset, all other points map to themselves. Offsets can be negative, so `T`
+/// must be signed.
+///
+/// This is the "destination start, source start, length" style of mapping, applied to whole
+/// intervals at once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PiecewiseShift<T = i64>
+where
+    T: PrimInt + Signed,
+{
+    // Sorted by source, sources are disjoint.
+    rules: Vec<(ClosedInterval<T>, T)>,
+}
+
+impl<T> PiecewiseShift<T>
+where
+    T: PrimInt + Signed,
+{
+    pub fn new() -> Self {
+        PiecewiseShift { rules: Vec::new() }
+    }
+
+    /// Move every point of `source` by `offset`. `source` must not overlap an existing rule.
+    pub fn insert(&mut self, source: ClosedInterval<T>, offset: T) {
+        let i = self.rules.partition_point(|(int, _)| int.b < source.a);
+        assert!(
+            self.rules.get(i).is_none_or(|(int, _)| source.b < int.a),
+            "source overlaps an existing rule"
+        );
+        self.rules.insert(i, (source, offset));
+    }
+
+    /// Map the `len` points starting at `source` to the points starting at `destination`.
+    pub fn insert_range(&mut self, destination: T, source: T, len: T) {
+        if len > T::zero() {
+            self.insert(
+                ClosedInterval::new(source, source + len - T::one()),
+                destination - source,
+            );
+        }
+    }
+
+    /// The source intervals and their offsets, in increasing order.
+    pub fn rules(&self) -> &[(ClosedInterval<T>, T)] {
+        &self.rules
+    }
+
+    /// Map a single point.
+    pub fn apply_point(&self, x: T) -> T {
+        let i = self.rules.partition_point(|(int, _)| int.b < x);
+        match self.rules.get(i) {
+            Some(&(int, offset)) if int.a <= x => x + offset,
+            _ => x,
+        }
+    }
+
+    /// Map `interval`, splitting it into the images of its mapped and unmapped parts. The
+    /// pieces are ordered by their position within `interval`.
+    pub fn apply(&self, interval: &ClosedInterval<T>) -> Vec<ClosedInterval<T>> {
+        self.split(interval)
+            .into_iter()
+            .map(|(int, offset)| ClosedInterval::new(int.a + offset, int.b + offset))
+            .collect()
+    }
+
+    /// Map every interval of `set`.
+    pub fn apply_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter().flat_map(|int| self.apply(int)).collect()
+    }
+
+    /// The map that applies `self` first and then `other`.
+    pub fn compose(&self, other: &PiecewiseShift<T>) -> PiecewiseShift<T> {
+        let mut rules = Vec::new();
+        for &(source, offset) in self.rules.iter() {
+            let image = ClosedInterval::new(source.a + offset, source.b + offset);
+            for (piece, second) in other.split(&image) {
+                let piece = ClosedInterval::new(piece.a - offset, piece.b - offset);
+                rules.push((piece, offset + second));
+            }
+        }
+        // Points not moved by `self` are only moved by `other`.
+        let sources: IntervalSet<T> = self.rules.iter().map(|(int, _)| *int).collect();
+        for (source, offset) in other.rules.iter() {
+            let mut free = IntervalSet::new();
+            free.insert(*source);
+            for piece in free.difference(&sources).iter() {
+                rules.push((*piece, *offset));
+            }
+        }
+        rules.retain(|(_, offset)| !offset.is_zero());
+        rules.sort_unstable();
+        PiecewiseShift { rules }
+    }
+
+    /// The inverse map, or `None` if `self` is not a bijection, i.e. if the images of the
+    /// source intervals do not exactly cover the source intervals.
+    pub fn invert(&self) -> Option<PiecewiseShift<T>> {
+        let sources: IntervalSet<T> = self.rules.iter().map(|(int, _)| *int).collect();
+        let mut rules: Vec<(ClosedInterval<T>, T)> = self
+            .rules
+            .iter()
+            .map(|&(int, offset)| (ClosedInterval::new(int.a + offset, int.b + offset), -offset))
+            .collect();
+        let images: IntervalSet<T> = rules.iter().map(|(int, _)| *int).collect();
+        // Shifting preserves lengths, so equal sets also rule out overlapping images.
+        if images != sources {
+            return None;
+        }
+        rules.sort_unstable();
+        Some(PiecewiseShift { rules })
+    }
+
+    /// Split `interval` into pieces with a single offset each, 0 for unmapped pieces.
+    fn split(&self, interval: &ClosedInterval<T>) -> Vec<(ClosedInterval<T>, T)> {
+        let mut pieces = Vec::new();
+        let mut start = interval.a;
+        let i = self.rules.partition_point(|(int, _)| int.b < interval.a);
+        for &(source, offset) in self.rules[i..].iter() {
+            if source.a > interval.b {
+                break;
+            }
+            if start < source.a {
+                pieces.push((ClosedInterval::new(start, source.a - T::one()), T::zero()));
+            }
+            let common = ClosedInterval::new(start.max(source.a), interval.b.min(source.b));
+            pieces.push((common, offset));
+            if common.b == interval.b {
+                return pieces;
+            }
+            start = common.b + T::one();
+        }
+        pieces.push((ClosedInterval::new(start, interval.b), T::zero()));
+        pieces
+    }
+}
+
+/// A multiset of closed intervals supporting stabbing and overlap queries in `O(log n + k)`.
+///
+/// Implemented as an AVL tree ordered by interval, where every node also stores the largest
+/// end point in its subtree. Equal intervals share a node with a count.
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T = i64>
+where
+    T: PrimInt,
+{
+    root: Link<T>,
+    len: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug, Clone)]
+struct Node<T>
+where
+    T: PrimInt,
+{
+    interval: ClosedInterval<T>,
+    // Number of copies of `interval`.
+    count: usize,
+    max: T,
+    height: u32,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> IntervalTree<T>
+where
+    T: PrimInt,
+{
+    pub fn new() -> Self {
+        IntervalTree { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add a copy of `interval`. Returns false if it is empty and was not added.
+    pub fn insert(&mut self, interval: ClosedInterval<T>) -> bool {
+        if interval.is_empty() {
+            return false;
+        }
+        self.root = Some(node_insert(self.root.take(), interval));
+        self.len += 1;
+        true
+    }
+
+    /// Remove one copy of `interval`. Returns false if it was not present.
+    pub fn remove(&mut self, interval: &ClosedInterval<T>) -> bool {
+        let (root, removed) = node_remove(self.root.take(), interval);
+        self.root = root;
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// All intervals containing `x`, in increasing order. Duplicates are reported once per copy.
+    pub fn stabbing(&self, x: T) -> Vec<ClosedInterval<T>> {
+        self.overlapping(&ClosedInterval::new(x, x))
+    }
+
+    /// All intervals sharing at least one point with `interval`, in increasing order. Duplicates
+    /// are reported once per copy.
+    pub fn overlapping(&self, interval: &ClosedInterval<T>) -> Vec<ClosedInterval<T>> {
+        let mut result = Vec::new();
+        if !interval.is_empty() {
+            collect_overlapping(&self.root, interval, &mut result);
+        }
+        result
+    }
+
+    /// Iterate over all intervals in increasing order, including duplicates.
+    pub fn iter(&self) -> IntervalTreeIterator<'_, T> {
+        let mut iter = IntervalTreeIterator {
+            stack: Vec::new(),
+            repeat: 0,
+        };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+impl<T> Default for IntervalTree<T>
+where
+    T: PrimInt,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<ClosedInterval<T>> for IntervalTree<T>
+where
+    T: PrimInt,
+{
+    fn from_iter<I: IntoIterator<Item = ClosedInterval<T>>>(iter: I) -> Self {
+        let mut tree = IntervalTree::new();
+        for int in iter {
+            tree.insert(int);
+        }
+        tree
+    }
+}
+
+/// Iterate over the intervals of an `IntervalTree` in increasing order.
+pub struct IntervalTreeIterator<'a, T>
+where
+    T: PrimInt,
+{
+    stack: Vec<&'a Node<T>>,
+    // Copies of the top of `stack` already returned.
+    repeat: usize,
+}
+
+impl<'a, T> IntervalTreeIterator<'a, T>
+where
+    T: PrimInt,
+{
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<T> Iterator for IntervalTreeIterator<'_, T>
+where
+    T: PrimInt,
+{
+    type Item = ClosedInterval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = *self.stack.last()?;
+        self.repeat += 1;
+        if self.repeat == node.count {
+            self.repeat = 0;
+            self.stack.pop();
+            self.push_left(&node.right);
+        }
+        Some(node.interval)
+    }
+}
+
+fn height<T: PrimInt>(link: &Link<T>) -> u32 {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+/// Recompute the cached height and maximum end point from the children.
+fn update<T: PrimInt>(node: &mut Node<T>) {
+    node.height = 1 + height(&node.left).max(height(&node.right));
+    node.max = [&node.left, &node.right]
+        .into_iter()
+        .flatten()
+        .fold(node.interval.b, |max, child| max.max(child.max));
+}
+
+fn rotate_right<T: PrimInt>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut left = node.left.take().expect("rotate_right without left child");
+    node.left = left.right.take();
+    update(&mut node);
+    left.right = Some(node);
+    update(&mut left);
+    left
+}
+
+fn rotate_left<T: PrimInt>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut right = node.right.take().expect("rotate_left without right child");
+    node.right = right.left.take();
+    update(&mut node);
+    right.left = Some(node);
+    update(&mut right);
+    right
+}
+
+/// Restore the AVL invariant at `node`, whose subtrees differ in height by at most 2.
+fn rebalance<T: PrimInt>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    update(&mut node);
+    let (hl, hr) = (height(&node.left), height(&node.right));
+    if hl > hr + 1 {
+        let left = node.left.take().unwrap();
+        node.left = Some(if height(&left.left) < height(&left.right) {
+            rotate_left(left)
+        } else {
+            left
+        });
+        return rotate_right(node);
+    }
+    if hr > hl + 1 {
+        let right = node.right.take().unwrap();
+        node.right = Some(if height(&right.right) < height(&right.left) {
+            rotate_right(right)
+        } else {
+            right
+        });
+        return rotate_left(node);
+    }
+    node
+}
+
+fn node_insert<T: PrimInt>(link: Link<T>, interval: ClosedInterval<T>) -> Box<Node<T>> {
+    let Some(mut node) = link else {
+        let leaf = Node {
+            interval,
+            count: 1,
+            max: interval.b,
+            height: 1,
+            left: None,
+            right: None,
+        };
+        return Box::new(leaf);
+    };
+    match interval.cmp(&node.interval) {
+        std::cmp::Ordering::Equal => {
+            node.count += 1;
+            return node;
+        }
+        std::cmp::Ordering::Less => node.left = Some(node_insert(node.left.take(), interval)),
+        std::cmp::Ordering::Greater => node.right = Some(node_insert(node.right.take(), interval)),
+    }
+    rebalance(node)
+}
+
+fn node_remove<T: PrimInt>(link: Link<T>, interval: &ClosedInterval<T>) -> (Link<T>, bool) {
+    let Some(mut node) = link else {
+        return (None, false);
+    };
+    match interval.cmp(&node.interval) {
+        std::cmp::Ordering::Less => {
+            let (left, removed) = node_remove(node.left.take(), interval);
+            node.left = left;
+            (Some(rebalance(node)), removed)
+        }
+        std::cmp::Ordering::Greater => {
+            let (right, removed) = node_remove(node.right.take(), interval);
+            node.right = right;
+            (Some(rebalance(node)), removed)
+        }
+        std::cmp::Ordering::Equal if node.count > 1 => {
+            node.count -= 1;
+            (Some(node), true)
+        }
+        std::cmp::Ordering::Equal => match (node.left.take(), node.right.take()) {
+            (None, right) => (right, true),
+            (left, None) => (left, true),
+            (left, Some(right)) => {
+                let (right, mut successor) = remove_min(right);
+                successor.left = left;
+                successor.right = right;
+                (Some(rebalance(successor)), true)
+            }
+        },
+    }
+}
+
+/// Detach the smallest node, returning the remaining subtree and the node.
+fn remove_min<T: PrimInt>(mut node: Box<Node<T>>) -> (Link<T>, Box<Node<T>>) {
+    match node.left.take() {
+        None => (node.right.take(), node),
+        Some(left) => {
+            let (left, min) = remove_min(left);
+            node.left = left;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+fn collect_overlapping<T: PrimInt>(
+    link: &Link<T>,
+    interval: &ClosedInterval<T>,
+    result: &mut Vec<ClosedInterval<T>>,
+) {
+    let Some(node) = link else {
+        return;
+    };
+    // Nothing in this subtree ends late enough to reach `interval`.
+    if node.max < interval.a {
+        return;
+    }
+    collect_overlapping(&node.left, interval, result);
+    if !node.interval.disjoint(interval) {
+        result.extend(std::iter::repeat_n(node.interval, node.count));
+    }
+    // Everything to the right starts after `node`, so it cannot overlap if `node` starts too late.
+    if node.interval.a <= interval.b {
+        collect_overlapping(&node.right, interval, result);
+    }
+}
+
+/// Coordinate compression of one axis: the integers between the smallest and the largest
+/// breakpoint are split into consecutive cells `[breakpoints[i], breakpoints[i + 1])`. A
+/// breakpoint past `T::MAX` is not representable, in that case the last cell ends at `T::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CompressedAxis<T = i64>
+where
+    T: PrimInt,
+{
+    breakpoints: Vec<T>,
+    // The last cell starts at the last breakpoint and ends at `T::MAX`.
+    unbounded: bool,
+}
+
+impl<T> CompressedAxis<T>
+where
+    T: PrimInt,
+{
+    /// Compress so that every interval is exactly covered by a run of cells.
+    pub fn from_intervals<I>(intervals: I) -> Self
+    where
+        I: IntoIterator<Item = ClosedInterval<T>>,
+    {
+        Self::from_breakpoints(
+            intervals
+                .into_iter()
+                .filter(|int| !int.is_empty())
+                .flat_map(|int| [Some(int.a), int.b.checked_add(&T::one())]),
+        )
+    }
+
+    /// Compress so that every value gets a cell of its own.
+    pub fn from_values<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_breakpoints(
+            values
+                .into_iter()
+                .flat_map(|v| [Some(v), v.checked_add(&T::one())]),
+        )
+    }
+
+    /// `None` stands for the breakpoint one past `T::MAX`.
+    fn from_breakpoints<I>(breakpoints: I) -> Self
+    where
+        I: IntoIterator<Item = Option<T>>,
+    {
+        let mut unbounded = false;
+        let mut breakpoints: Vec<T> = breakpoints
+            .into_iter()
+            .filter_map(|bp| {
+                unbounded |= bp.is_none();
+                bp
+            })
+            .collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+        CompressedAxis {
+            breakpoints,
+            unbounded,
+        }
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        match self.breakpoints.len() {
+            0 => 0,
+            n => n - 1 + usize::from(self.unbounded),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Index of the cell containing `x`.
+    pub fn index(&self, x: T) -> Option<usize> {
+        let i = self.breakpoints.partition_point(|&bp| bp <= x);
+        (1..=self.len()).contains(&i).then(|| i - 1)
+    }
+
+    /// The integers covered by cell `i`.
+    pub fn cell(&self, i: usize) -> ClosedInterval<T> {
+        assert!(i < self.len(), "cell out of bounds");
+        let end = self
+            .breakpoints
+            .get(i + 1)
+            .map_or(T::max_value(), |&bp| bp - T::one());
+        ClosedInterval::new(self.breakpoints[i], end)
+    }
+
+    /// Number of integers covered by cell `i`.
+    pub fn weight(&self, i: usize) -> usize {
+        self.cell(i).len()
+    }
+}
+
+/// Coordinate compression of the plane, one `CompressedAxis` per dimension. Every compressed
+/// cell stands for a rectangle of real points, so a compressed `Grid` can be flood-filled and
+/// the real area recovered with `area`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CompressedGrid<T = i64>
+where
+    T: PrimInt + Integer + Signed,
+{
+    pub x: CompressedAxis<T>,
+    pub y: CompressedAxis<T>,
+}
+
+impl<T> CompressedGrid<T>
+where
+    T: PrimInt + Integer + Signed,
+{
+    /// Compress so that every point gets a cell of its own. One extra cell is added before and
+    /// after the points on both axes, so that a flood fill from `(0, 0)` can get around them.
+    /// The extra cells are left out where they would lie outside the range of `T`.
+    pub fn from_points<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Point2D<T>>,
+    {
+        let (xs, ys): (Vec<T>, Vec<T>) = points.into_iter().map(|p| (p.x, p.y)).unzip();
+        let with_margin = |values: Vec<T>| {
+            let min = values.iter().min().copied();
+            let max = values.iter().max().copied();
+            let margin = [
+                min.and_then(|v| v.checked_sub(&T::one())),
+                max.and_then(|v| v.checked_add(&T::one())),
+            ];
+            CompressedAxis::from_values(values.into_iter().chain(margin.into_iter().flatten()))
+        };
+        CompressedGrid {
+            x: with_margin(xs),
+            y: with_margin(ys),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.x.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.y.len()
+    }
+
+    /// The compressed cell containing `p`.
+    pub fn compress(&self, p: Point2D<T>) -> Option<Point2D> {
+        Some(Point2D::new(
+            self.x.index(p.x)? as i64,
+            self.y.index(p.y)? as i64,
+        ))
+    }
+
+    /// The real top-left point of compressed cell `p`.
+    pub fn decompress(&self, p: Point2D) -> Point2D<T> {
+        Point2D::new(self.x.cell(p.x as usize).a, self.y.cell(p.y as usize).a)
+    }
+
+    /// Number of real points in compressed cell `p`.
+    pub fn area(&self, p: Point2D) -> usize {
+        self.x.weight(p.x as usize) * self.y.weight(p.y as usize)
+    }
+
+    /// A compressed grid with every cell set to `value`.
+    pub fn to_grid<V: Clone>(&self, value: V) -> Grid<V> {
+        Grid::filled(self.width(), self.height(), value)
+    }
+
+    /// Sum the real area of all cells of the compressed `grid` satisfying `predicate`.
+    pub fn total_area<V, P>(&self, grid: &Grid<V>, predicate: P) -> usize
+    where
+        P: Fn(&V) -> bool,
+    {
+        grid.iter()
+            .filter(|(_, cell)| predicate(cell))
+            .map(|(p, _)| self.area(p))
+            .sum()
+    }
+}
+
+/// An axis-aligned box of lattice points in `N` dimensions, one closed interval per axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<const N: usize, T = i64>
+where
+    T: PrimInt,
+{
+    pub axes: [ClosedInterval<T>; N],
+}
+
+impl<const N: usize, T> IntervalBox<N, T>
+where
+    T: PrimInt,
+{
+    pub fn new(axes: [ClosedInterval<T>; N]) -> Self {
+        IntervalBox { axes }
+    }
+
+    /// Count (whole) points in the box.
+    pub fn volume(&self) -> usize {
+        self.axes.iter().map(|axis| axis.len()).product()
+    }
+
+    /// Test if `other` is a subset of `self`.
+    pub fn contains(&self, other: &IntervalBox<N, T>) -> bool {
+        self.axes
+            .iter()
+            .zip(other.axes.iter())
+            .all(|(a, b)| a.contains(b))
+    }
+
+    /// Intersect with box.
+    pub fn intersect(&self, other: &IntervalBox<N, T>) -> Option<IntervalBox<N, T>> {
+        let mut axes = self.axes;
+        for (axis, o) in axes.iter_mut().zip(other.axes.iter()) {
+            *axis = axis.intersect(o)?;
+        }
+        Some(IntervalBox { axes })
+    }
+}
+
+/// The union of boxes under a sequence of add and remove operations.
+///
+/// The union is kept as a signed sum of boxes (inclusion-exclusion): adding a box cancels its
+/// overlap with everything added so far before counting it once. Equal boxes are merged, so
+/// repeated overlaps do not blow up the number of terms.
+#[derive(Debug, Clone, Default)]
+pub struct BoxUnion<const N: usize, T = i64>
+where
+    T: PrimInt + Hash,
+{
+    terms: HashMap<IntervalBox<N, T>, i64>,
+}
+
+impl<const N: usize, T> BoxUnion<N, T>
+where
+    T: PrimInt + Hash,
+{
+    pub fn new() -> Self {
+        BoxUnion {
+            terms: HashMap::new(),
+        }
+    }
+
+    /// Add all points of `b` to the union.
+    pub fn add(&mut self, b: IntervalBox<N, T>) {
+        self.remove(b);
+        *self.terms.entry(b).or_insert(0) += 1;
+    }
+
+    /// Remove all points of `b` from the union.
+    pub fn remove(&mut self, b: IntervalBox<N, T>) {
+        let mut updates: HashMap<IntervalBox<N, T>, i64> = HashMap::new();
+        for (term, &sign) in self.terms.iter() {
+            if let Some(common) = term.intersect(&b) {
+                *updates.entry(common).or_insert(0) -= sign;
+            }
+        }
+        for (term, delta) in updates {
+            let sign = self.terms.entry(term).or_insert(0);
+            *sign += delta;
+            if *sign == 0 {
+                self.terms.remove(&term);
+            }
+        }
+    }
+
+    /// Count the points in the union.
+    pub fn volume(&self) -> usize {
+        let total: i64 = self
+            .terms
+            .iter()
+            .map(|(term, &sign)| sign * term.volume() as i64)
+            .sum();
+        total as usize
+    }
+}
+
+/// Count the points covered by at least one of `boxes`.
+pub fn union_volume<const N: usize, T>(boxes: &[IntervalBox<N, T>]) -> usize
+where
+    T: PrimInt + Hash,
+{
+    let mut union = BoxUnion::new();
+    for &b in boxes {
+        union.add(b);
+    }
+    union.volume()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid;
 
     #[test]
     fn test_contains() {
         let x = ClosedInterval::new(2, 5);
         let y = ClosedInterval::new(3, 5);
-        assert_eq!(true, x.contains(&x));
-        assert_eq!(true, x.contains(&y));
-        assert_eq!(false, y.contains(&x));
+        assert!(x.contains(&x));
+        assert!(x.contains(&y));
+        assert!(!y.contains(&x));
 
         let z = ClosedInterval::new(3, 6);
-        assert_eq!(false, x.contains(&z));
+        assert!(!x.contains(&z));
     }
 
     #[test]
@@ -102,10 +1139,10 @@ mod tests {
         let x = ClosedInterval::new(2, 5);
         let y = ClosedInterval::new(3, 6);
         let z = ClosedInterval::new(7, 8);
-        assert_eq!(false, x.disjoint(&y));
-        assert_eq!(false, y.disjoint(&x));
-        assert_eq!(true, y.disjoint(&z));
-        assert_eq!(true, z.disjoint(&y));
+        assert!(!x.disjoint(&y));
+        assert!(!y.disjoint(&x));
+        assert!(y.disjoint(&z));
+        assert!(z.disjoint(&y));
     }
 
     #[test]
@@ -143,9 +1180,460 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_points() {
+        let x = ClosedInterval::new(2, 5);
+        assert!(x.contains_point(2) && x.contains_point(5) && !x.contains_point(6));
+        assert_eq!(vec![2, 3, 4, 5], x.iter().collect::<Vec<_>>());
+        assert_eq!(0, ClosedInterval::<i64>::empty().iter().count());
+        assert_eq!(256, ClosedInterval::<u8>::new(0, 255).iter().count());
+    }
+
     #[test]
     fn test_len() {
         assert_eq!(3, ClosedInterval::new(1, 3).len());
         assert_eq!(6, ClosedInterval::new(-2, 3).len());
     }
+
+    #[test]
+    fn test_interval_box() {
+        let b1 = IntervalBox::new([ClosedInterval::new(0, 2), ClosedInterval::new(0, 2)]);
+        let b2 = IntervalBox::new([ClosedInterval::new(1, 3), ClosedInterval::new(2, 5)]);
+        assert_eq!(9, b1.volume());
+        assert_eq!(
+            Some(IntervalBox::new([
+                ClosedInterval::new(1, 2),
+                ClosedInterval::new(2, 2)
+            ])),
+            b1.intersect(&b2)
+        );
+        assert!(!b1.contains(&b2));
+        assert!(b1.contains(&b1.intersect(&b2).unwrap()));
+        assert_eq!(9 + 12 - 2, union_volume(&[b1, b2]));
+    }
+
+    #[test]
+    fn test_box_union() {
+        // Example from AoC 2021, day 22.
+        let cube = |a: i64, b: i64| IntervalBox::new([ClosedInterval::new(a, b); 3]);
+        let mut union = BoxUnion::new();
+        union.add(IntervalBox::new([
+            ClosedInterval::new(10, 12),
+            ClosedInterval::new(10, 12),
+            ClosedInterval::new(10, 12),
+        ]));
+        union.add(IntervalBox::new([
+            ClosedInterval::new(11, 13),
+            ClosedInterval::new(11, 13),
+            ClosedInterval::new(11, 13),
+        ]));
+        union.remove(IntervalBox::new([
+            ClosedInterval::new(9, 11),
+            ClosedInterval::new(9, 11),
+            ClosedInterval::new(9, 11),
+        ]));
+        union.add(cube(10, 10));
+        assert_eq!(39, union.volume());
+
+        union.remove(cube(-100, 100));
+        assert_eq!(0, union.volume());
+        union.add(cube(0, 1));
+        union.add(cube(0, 1));
+        assert_eq!(8, union.volume());
+    }
+
+    #[test]
+    fn test_interval_set_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(ClosedInterval::new(10, 12));
+        set.insert(ClosedInterval::new(1, 3));
+        set.insert(ClosedInterval::new(5, 6));
+        assert_eq!(8, set.len());
+        set.insert(ClosedInterval::new(4, 4));
+        assert_eq!(
+            &[ClosedInterval::new(1, 6), ClosedInterval::new(10, 12)],
+            set.intervals()
+        );
+        set.insert(ClosedInterval::new(0, 20));
+        assert_eq!(&[ClosedInterval::new(0, 20)], set.intervals());
+        assert!(set.contains(0) && set.contains(20) && !set.contains(21));
+    }
+
+    #[test]
+    fn test_interval_set_remove() {
+        let mut set: IntervalSet = [ClosedInterval::new(0, 10), ClosedInterval::new(20, 30)]
+            .into_iter()
+            .collect();
+        set.remove(ClosedInterval::new(5, 22));
+        assert_eq!(
+            &[ClosedInterval::new(0, 4), ClosedInterval::new(23, 30)],
+            set.intervals()
+        );
+        set.remove(ClosedInterval::new(2, 2));
+        assert_eq!(3, set.intervals().len());
+        assert!(!set.contains(2));
+        set.remove(ClosedInterval::new(-5, 100));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_interval_set_algebra() {
+        let a: IntervalSet = [ClosedInterval::new(0, 5), ClosedInterval::new(10, 15)]
+            .into_iter()
+            .collect();
+        let b: IntervalSet = [ClosedInterval::new(3, 12)].into_iter().collect();
+        assert_eq!(&[ClosedInterval::new(0, 15)], a.union(&b).intervals());
+        assert_eq!(
+            &[ClosedInterval::new(3, 5), ClosedInterval::new(10, 12)],
+            a.intersection(&b).intervals()
+        );
+        assert_eq!(
+            &[ClosedInterval::new(0, 2), ClosedInterval::new(13, 15)],
+            a.difference(&b).intervals()
+        );
+        assert_eq!(
+            vec![ClosedInterval::new(6, 9)],
+            a.gaps().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_piecewise_shift_apply() {
+        // Seed-to-soil map from AoC 2023, day 5.
+        let mut map = PiecewiseShift::new();
+        map.insert_range(50, 98, 2);
+        map.insert_range(52, 50, 48);
+        assert_eq!(81, map.apply_point(79));
+        assert_eq!(14, map.apply_point(14));
+        assert_eq!(50, map.apply_point(98));
+        assert_eq!(
+            vec![
+                ClosedInterval::new(40, 49),
+                ClosedInterval::new(52, 99),
+                ClosedInterval::new(50, 51),
+                ClosedInterval::new(100, 110),
+            ],
+            map.apply(&ClosedInterval::new(40, 110))
+        );
+        assert_eq!(
+            vec![ClosedInterval::new(57, 60)],
+            map.apply(&ClosedInterval::new(55, 58))
+        );
+        let set = map.apply_set(&[ClosedInterval::new(40, 110)].into_iter().collect());
+        assert_eq!(&[ClosedInterval::new(40, 110)], set.intervals());
+    }
+
+    #[test]
+    fn test_piecewise_shift_compose_invert() {
+        let mut first = PiecewiseShift::new();
+        first.insert_range(50, 98, 2);
+        first.insert_range(52, 50, 48);
+        let mut second = PiecewiseShift::new();
+        second.insert_range(0, 15, 37);
+        second.insert_range(37, 52, 2);
+        second.insert_range(39, 0, 15);
+        let composed = first.compose(&second);
+        for x in -5..120 {
+            assert_eq!(
+                second.apply_point(first.apply_point(x)),
+                composed.apply_point(x),
+                "x = {}",
+                x
+            );
+        }
+
+        let inverse = composed.invert().unwrap();
+        for x in -5..120 {
+            assert_eq!(x, inverse.apply_point(composed.apply_point(x)));
+        }
+
+        let mut collapse = PiecewiseShift::new();
+        collapse.insert(ClosedInterval::new(0, 4), 5);
+        assert_eq!(None, collapse.invert());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_piecewise_shift_overlap() {
+        let mut map = PiecewiseShift::new();
+        map.insert_range(50, 98, 2);
+        map.insert_range(0, 90, 10);
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty: ClosedInterval = ClosedInterval::empty();
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.len());
+        assert_eq!(0, ClosedInterval::new(5, 2).len());
+        assert!(ClosedInterval::new(2, 5).contains(&empty));
+        assert!(empty.disjoint(&ClosedInterval::new(-10, 10)));
+        assert_eq!(
+            None,
+            ClosedInterval::new(2, 5).intersect(&ClosedInterval::new(6, 2))
+        );
+        assert_eq!(
+            vec![ClosedInterval::new(1, 3)],
+            merge_intervals(&[ClosedInterval::new(5, 4), ClosedInterval::new(1, 3), empty])
+        );
+        assert_eq!(Vec::<ClosedInterval>::new(), merge_intervals(&[empty]));
+
+        let mut set = IntervalSet::new();
+        set.insert(empty);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_generic() {
+        let x: ClosedInterval<u8> = ClosedInterval::new(0, 255);
+        assert_eq!(256, x.len());
+        assert_eq!(256, ClosedInterval::<i8>::new(-128, 127).len());
+        assert_eq!(254, HalfOpenInterval::<i8>::new(-127, 127).len());
+        let y = ClosedInterval::<usize>::from(3..=7);
+        assert_eq!(
+            Some(ClosedInterval::new(5, 7)),
+            y.intersect(&ClosedInterval::new(5, 9))
+        );
+        assert_eq!(
+            vec![ClosedInterval::<u32>::new(1, 6)],
+            merge_intervals(&[ClosedInterval::new(1, 3), ClosedInterval::new(2, 6)])
+        );
+    }
+
+    #[test]
+    fn test_half_open() {
+        let x = HalfOpenInterval::from(2..5);
+        assert_eq!(3, x.len());
+        assert!(HalfOpenInterval::new(3, 3).is_empty());
+        assert!(x.disjoint(&HalfOpenInterval::new(5, 8)));
+        assert_eq!(
+            Some(HalfOpenInterval::new(4, 5)),
+            x.intersect(&HalfOpenInterval::new(4, 8))
+        );
+        assert_eq!(ClosedInterval::new(2, 4), ClosedInterval::from(x));
+        assert_eq!(x, HalfOpenInterval::from(ClosedInterval::new(2, 4)));
+        assert!(ClosedInterval::from(HalfOpenInterval::<u32>::new(0, 0)).is_empty());
+        assert!(HalfOpenInterval::from(ClosedInterval::<u32>::empty()).is_empty());
+        assert_eq!(
+            HalfOpenInterval::new(0, 255),
+            HalfOpenInterval::from(ClosedInterval::<u8>::new(0, 254))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_half_open_from_max() {
+        let _ = HalfOpenInterval::from(ClosedInterval::<u8>::new(0, u8::MAX));
+    }
+
+    #[test]
+    fn test_interval_tree_queries() {
+        let tree: IntervalTree = [
+            ClosedInterval::new(15, 20),
+            ClosedInterval::new(10, 30),
+            ClosedInterval::new(17, 19),
+            ClosedInterval::new(5, 20),
+            ClosedInterval::new(12, 15),
+            ClosedInterval::new(30, 40),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(6, tree.len());
+        assert_eq!(
+            vec![
+                ClosedInterval::new(5, 20),
+                ClosedInterval::new(10, 30),
+                ClosedInterval::new(15, 20),
+                ClosedInterval::new(17, 19),
+            ],
+            tree.stabbing(18)
+        );
+        assert_eq!(
+            vec![ClosedInterval::new(10, 30), ClosedInterval::new(30, 40)],
+            tree.overlapping(&ClosedInterval::new(25, 35))
+        );
+        assert!(tree.stabbing(41).is_empty());
+        assert!(tree.overlapping(&ClosedInterval::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_interval_tree_insert_remove() {
+        let mut tree = IntervalTree::new();
+        for i in 0..100 {
+            assert!(tree.insert(ClosedInterval::new(i, i + 10)));
+        }
+        assert!(!tree.insert(ClosedInterval::new(5, 4)));
+        assert_eq!(100, tree.len());
+        // The tree stays balanced under sorted insertion.
+        assert!(height(&tree.root) <= 10);
+
+        for i in (0..100).step_by(2) {
+            assert!(tree.remove(&ClosedInterval::new(i, i + 10)));
+        }
+        assert!(!tree.remove(&ClosedInterval::new(0, 10)));
+        assert_eq!(50, tree.len());
+        assert_eq!(50, tree.iter().count());
+        assert!(tree.iter().zip(tree.iter().skip(1)).all(|(x, y)| x < y));
+        assert_eq!(
+            vec![
+                ClosedInterval::new(41, 51),
+                ClosedInterval::new(43, 53),
+                ClosedInterval::new(45, 55),
+                ClosedInterval::new(47, 57),
+                ClosedInterval::new(49, 59),
+            ],
+            tree.stabbing(50)
+        );
+        for i in (1..100).step_by(2) {
+            tree.remove(&ClosedInterval::new(i, i + 10));
+        }
+        assert!(tree.is_empty());
+        assert!(tree.stabbing(50).is_empty());
+    }
+
+    #[test]
+    fn test_compressed_axis() {
+        let axis = CompressedAxis::from_intervals([
+            ClosedInterval::new(0, 9),
+            ClosedInterval::new(5, 100),
+            ClosedInterval::new(3, 2),
+        ]);
+        assert_eq!(3, axis.len());
+        assert_eq!(
+            vec![
+                ClosedInterval::new(0, 4),
+                ClosedInterval::new(5, 9),
+                ClosedInterval::new(10, 100)
+            ],
+            (0..axis.len()).map(|i| axis.cell(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(1), axis.index(7));
+        assert_eq!(Some(2), axis.index(100));
+        assert_eq!(None, axis.index(101));
+        assert_eq!(None, axis.index(-1));
+        assert_eq!(91, axis.weight(2));
+
+        let axis = CompressedAxis::from_values([10, 1_000_000]);
+        assert_eq!(3, axis.len());
+        assert_eq!(1, axis.weight(0));
+        assert_eq!(999_989, axis.weight(1));
+    }
+
+    #[test]
+    fn test_compressed_grid_flood_fill() {
+        // A dig plan: the trench along the polygon and its interior are dug out.
+        let corners = [
+            Point2D::new(0, 0),
+            Point2D::new(6_000, 0),
+            Point2D::new(6_000, 5_000),
+            Point2D::new(2_000, 5_000),
+            Point2D::new(2_000, 2_000),
+            Point2D::new(0, 2_000),
+        ];
+        let compressed = CompressedGrid::from_points(corners);
+        let mut grid = compressed.to_grid(b'.');
+        for (i, &a) in corners.iter().enumerate() {
+            let a = compressed.compress(a).unwrap();
+            let b = compressed
+                .compress(corners[(i + 1) % corners.len()])
+                .unwrap();
+            let (min, max) = (a.component_min(b), a.component_max(b));
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    grid[Point2D::new(x, y)] = b'#';
+                }
+            }
+        }
+
+        let mut todo = vec![Point2D::new(0, 0)];
+        while let Some(p) = todo.pop() {
+            if grid[p] == b'.' {
+                grid[p] = b'o';
+                todo.extend(grid::neighbors4(p).filter(|&q| grid.in_bounds(q)));
+            }
+        }
+        let expected = 6_001 * 2_001 + 4_001 * 3_000;
+        assert_eq!(expected, compressed.total_area(&grid, |&c| c != b'o'));
+        assert_eq!(
+            Point2D::new(2_000, 0),
+            compressed.decompress(compressed.compress(Point2D::new(2_000, 0)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_generic_containers() {
+        let mut set: IntervalSet<u32> = IntervalSet::new();
+        set.insert(ClosedInterval::new(0, 3));
+        set.insert(ClosedInterval::new(u32::MAX - 1, u32::MAX));
+        set.remove(ClosedInterval::new(0, 0));
+        assert_eq!(5, set.len());
+        assert!(!set.contains(0) && set.contains(u32::MAX));
+
+        let mut map: PiecewiseShift<i32> = PiecewiseShift::new();
+        map.insert_range(0, 10, 5);
+        map.insert_range(10, 0, 5);
+        assert_eq!(2, map.apply_point(12));
+        assert_eq!(Some(10), map.invert().map(|inv| inv.apply_point(0)));
+
+        let axis: CompressedAxis<usize> = CompressedAxis::from_values([3, 10]);
+        assert_eq!(Some(1), axis.index(5));
+        assert_eq!(6, axis.weight(1));
+
+        let b = IntervalBox::new([ClosedInterval::<u8>::new(0, 9); 2]);
+        assert_eq!(
+            100 + 100 - 25,
+            union_volume(&[b, IntervalBox::new([ClosedInterval::new(5, 14); 2])])
+        );
+    }
+
+    #[test]
+    fn test_interval_tree_duplicates() {
+        // Two sensors covering the same span.
+        let mut tree: IntervalTree = [
+            ClosedInterval::new(0, 10),
+            ClosedInterval::new(5, 8),
+            ClosedInterval::new(0, 10),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(3, tree.len());
+        assert_eq!(3, tree.iter().count());
+        assert_eq!(
+            vec![ClosedInterval::new(0, 10), ClosedInterval::new(0, 10)],
+            tree.stabbing(2)
+        );
+        assert_eq!(3, tree.overlapping(&ClosedInterval::new(8, 20)).len());
+
+        assert!(tree.remove(&ClosedInterval::new(0, 10)));
+        assert_eq!(vec![ClosedInterval::new(0, 10)], tree.stabbing(2));
+        assert!(tree.remove(&ClosedInterval::new(0, 10)));
+        assert!(!tree.remove(&ClosedInterval::new(0, 10)));
+        assert!(tree.stabbing(2).is_empty());
+        assert_eq!(1, tree.len());
+    }
+
+    #[test]
+    fn test_compressed_axis_limits() {
+        let axis = CompressedAxis::<u8>::from_values([255]);
+        assert_eq!(1, axis.len());
+        assert_eq!(Some(0), axis.index(255));
+        assert_eq!(ClosedInterval::new(255, 255), axis.cell(0));
+
+        let axis = CompressedAxis::<u8>::from_values([0, 255]);
+        assert_eq!(
+            vec![1, 254, 1],
+            (0..axis.len()).map(|i| axis.weight(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, CompressedAxis::<u8>::from_values([0, 10]).index(11));
+
+        let axis = CompressedAxis::from_intervals([ClosedInterval::<i8>::new(120, 127)]);
+        assert_eq!(1, axis.len());
+        assert_eq!(8, axis.weight(0));
+        assert_eq!(None, axis.index(119));
+
+        let grid = CompressedGrid::<i8>::from_points([Point2D::new(-128, 0), Point2D::new(127, 5)]);
+        assert_eq!((3, 5), (grid.width(), grid.height()));
+        let all = grid.to_grid(());
+        assert_eq!(256 * 8, grid.total_area(&all, |_| true));
+    }
 }
@@ -1,9 +1,14 @@
+pub mod automaton;
 pub mod bitset;
 pub mod convert;
+pub mod direction;
 pub mod graph;
+pub mod grid;
 pub mod interval;
 pub mod io;
 pub mod math;
 pub mod parse;
 pub mod point;
+pub mod rect;
 pub mod search;
+pub mod segment;
@@ -39,7 +39,7 @@ pub fn integer(input: &[u8], ignore_prefix: bool) -> ParseResult<&[u8], i64> {
     let mut start = 0;
     if ignore_prefix {
         for (i, &b) in input.iter().enumerate() {
-            if (b'0'..=b'9').contains(&b) {
+            if b.is_ascii_digit() {
                 start = i;
                 break;
             }
@@ -184,7 +184,7 @@ mod tests {
         let s = "-123";
         let (rest, val) = integer(s.as_bytes(), false).unwrap();
         assert_eq!(-123, val);
-        assert_eq!(true, rest.is_empty());
+        assert!(rest.is_empty());
     }
 
     #[test]
@@ -4,6 +4,9 @@ use core::ops;
 use num_integer::Integer;
 use num_traits::Signed;
 
+use crate::direction::Direction8;
+use crate::parse::ParseResult;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash, Default)]
 pub struct Point2D<T = i64>
 where
@@ -32,32 +35,58 @@ where
         let dy = self.y - other.y;
         dx * dx + dy * dy
     }
-}
 
-impl<T> ops::Add<Point2D<T>> for Point2D<T>
-where
-    T: Integer + Signed + Copy,
-{
-    type Output = Point2D<T>;
+    /// Move `n` steps in direction `dir`.
+    pub fn step<D: Into<Direction8>>(&self, dir: D, n: T) -> Self {
+        let d: Point2D<T> = dir.into().offset();
+        *self + d * n
+    }
 
-    fn add(self, rhs: Point2D<T>) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    /// Rotate by 90 degrees clockwise around the origin (`y` grows downwards, as in `grid`).
+    pub fn rotate_right(&self) -> Self {
+        Point2D {
+            x: -self.y,
+            y: self.x,
         }
     }
-}
 
-impl<T> ops::Sub<Point2D<T>> for Point2D<T>
-where
-    T: Integer + Signed + Copy,
-{
-    type Output = Point2D<T>;
+    /// Rotate by 90 degrees counterclockwise around the origin (`y` grows downwards).
+    pub fn rotate_left(&self) -> Self {
+        Point2D {
+            x: self.y,
+            y: -self.x,
+        }
+    }
 
-    fn sub(self, rhs: Point2D<T>) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    /// Rotate by `quarter_turns` times 90 degrees around the origin, clockwise for positive
+    /// values.
+    pub fn rotate(&self, quarter_turns: i32) -> Self {
+        match quarter_turns.rem_euclid(4) {
+            0 => *self,
+            1 => self.rotate_right(),
+            2 => -*self,
+            _ => self.rotate_left(),
+        }
+    }
+
+    /// Rotate by `quarter_turns` times 90 degrees around `pivot`.
+    pub fn rotate_around(&self, pivot: Point2D<T>, quarter_turns: i32) -> Self {
+        pivot + (*self - pivot).rotate(quarter_turns)
+    }
+
+    /// Mirror across the y-axis, negating `x`.
+    pub fn reflect_x(&self) -> Self {
+        Point2D {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+
+    /// Mirror across the x-axis, negating `y`.
+    pub fn reflect_y(&self) -> Self {
+        Point2D {
+            x: self.x,
+            y: -self.y,
         }
     }
 }
@@ -72,58 +101,787 @@ where
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash, Default)]
-pub struct Point3D {
-    pub x: i64,
-    pub y: i64,
-    pub z: i64,
+pub struct Point3D<T = i64>
+where
+    T: Integer + Signed + Copy,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
 }
 
-impl Point3D {
-    pub fn new(x: i64, y: i64, z: i64) -> Self {
+impl<T> Point3D<T>
+where
+    T: Integer + Signed + Copy,
+{
+    pub fn new(x: T, y: T, z: T) -> Self {
         Point3D { x, y, z }
     }
 
     /// Compute the Manhattan distance between `self` and `other`.
-    pub fn manhattan(&self, other: &Point3D) -> i64 {
+    pub fn manhattan(&self, other: &Point3D<T>) -> T {
         (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
     }
 
     /// Compute the Euclidean squared distance between `self` and `other`.
-    pub fn euclidean_squared(&self, other: &Point3D) -> i64 {
+    pub fn euclidean_squared(&self, other: &Point3D<T>) -> T {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
         let dz = self.z - other.z;
         dx * dx + dy * dy + dz * dz
     }
+
+    /// Compute the Chebyshev distance between `self` and `other`, i.e. the number of king moves.
+    pub fn chebyshev(&self, other: &Point3D<T>) -> T {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    /// Compute the cross product of `self` and `other`.
+    pub fn cross(&self, other: &Point3D<T>) -> Point3D<T> {
+        Point3D {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Iterate over the 6 neighbors sharing a face with `self`.
+    pub fn neighbors6(&self) -> impl Iterator<Item = Point3D<T>> {
+        let (zero, one) = (T::zero(), T::one());
+        let p = *self;
+        [
+            Point3D::new(one, zero, zero),
+            Point3D::new(-one, zero, zero),
+            Point3D::new(zero, one, zero),
+            Point3D::new(zero, -one, zero),
+            Point3D::new(zero, zero, one),
+            Point3D::new(zero, zero, -one),
+        ]
+        .into_iter()
+        .map(move |d| p + d)
+    }
+
+    /// Iterate over the 26 neighbors sharing a face, edge or corner with `self`.
+    pub fn neighbors26(&self) -> impl Iterator<Item = Point3D<T>> {
+        let deltas = [-T::one(), T::zero(), T::one()];
+        let p = *self;
+        deltas
+            .into_iter()
+            .flat_map(move |dx| {
+                deltas
+                    .into_iter()
+                    .flat_map(move |dy| deltas.into_iter().map(move |dz| Point3D::new(dx, dy, dz)))
+            })
+            .filter(|d| !(d.x.is_zero() && d.y.is_zero() && d.z.is_zero()))
+            .map(move |d| p + d)
+    }
+}
+
+impl<T> fmt::Display for Point3D<T>
+where
+    T: Integer + Signed + Copy + fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
 }
 
-impl ops::Add<Point3D> for Point3D {
-    type Output = Point3D;
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash, Default)]
+pub struct Point4D<T = i64>
+where
+    T: Integer + Signed + Copy,
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T> Point4D<T>
+where
+    T: Integer + Signed + Copy,
+{
+    pub fn new(x: T, y: T, z: T, w: T) -> Self {
+        Point4D { x, y, z, w }
+    }
 
-    fn add(self, rhs: Point3D) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
+    /// Compute the Manhattan distance between `self` and `other`.
+    pub fn manhattan(&self, other: &Point4D<T>) -> T {
+        (self.x - other.x).abs()
+            + (self.y - other.y).abs()
+            + (self.z - other.z).abs()
+            + (self.w - other.w).abs()
+    }
+
+    /// Compute the Euclidean squared distance between `self` and `other`.
+    pub fn euclidean_squared(&self, other: &Point4D<T>) -> T {
+        let d = *self - *other;
+        d.dot(d)
+    }
+}
+
+impl<T> fmt::Display for Point4D<T>
+where
+    T: Integer + Signed + Copy + fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.x, self.y, self.z, self.w)
+    }
+}
+
+/// One of the 24 axis-aligned rotations of 3D space.
+///
+/// Applying it maps component `i` of the result to `±p[axes[i]]`, so orientations can be
+/// composed and inverted without touching any point.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
+pub struct Orientation {
+    axes: [usize; 3],
+    negate: [bool; 3],
+}
+
+impl Orientation {
+    pub const IDENTITY: Orientation = Orientation {
+        axes: [0, 1, 2],
+        negate: [false; 3],
+    };
+
+    /// Enumerate all 24 rotations, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        const PERMUTATIONS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], false),
+            ([0, 2, 1], true),
+            ([1, 0, 2], true),
+            ([1, 2, 0], false),
+            ([2, 0, 1], false),
+            ([2, 1, 0], true),
+        ];
+        PERMUTATIONS.into_iter().flat_map(|(axes, odd)| {
+            (0..8_u8).filter_map(move |signs| {
+                let negate = [signs & 1 != 0, signs & 2 != 0, signs & 4 != 0];
+                // A rotation has determinant +1: odd permutations need an odd number of flips.
+                let flips = negate.iter().filter(|&&n| n).count();
+                (odd == (flips % 2 == 1)).then_some(Orientation { axes, negate })
+            })
+        })
+    }
+
+    /// Rotate `p`.
+    pub fn apply<T>(&self, p: Point3D<T>) -> Point3D<T>
+    where
+        T: Integer + Signed + Copy,
+    {
+        let coords = [p.x, p.y, p.z];
+        let component = |i: usize| {
+            let v = coords[self.axes[i]];
+            if self.negate[i] {
+                -v
+            } else {
+                v
+            }
+        };
+        Point3D::new(component(0), component(1), component(2))
+    }
+
+    /// The rotation that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Orientation) -> Orientation {
+        let mut result = Orientation::IDENTITY;
+        for i in 0..3 {
+            let j = self.axes[i];
+            result.axes[i] = other.axes[j];
+            result.negate[i] = self.negate[i] != other.negate[j];
+        }
+        result
+    }
+
+    /// The rotation that undoes `self`.
+    pub fn inverse(&self) -> Orientation {
+        let mut result = Orientation::IDENTITY;
+        for i in 0..3 {
+            let j = self.axes[i];
+            result.axes[j] = i;
+            result.negate[j] = self.negate[i];
         }
+        result
     }
 }
 
-impl ops::Sub<Point3D> for Point3D {
-    type Output = Point3D;
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation::IDENTITY
+    }
+}
 
-    fn sub(self, rhs: Point3D) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
+/// A hexagon in axial coordinates on a pointy-top hex grid, where `r` grows towards the south.
+/// The third cube coordinate is `s = -q - r`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash, Default)]
+pub struct Hex<T = i64>
+where
+    T: Integer + Signed + Copy,
+{
+    pub q: T,
+    pub r: T,
+}
+
+/// One of the six neighbors of a hexagon on a pointy-top hex grid.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    /// All directions, clockwise starting east.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Parse one of `e`, `se`, `sw`, `w`, `nw` or `ne`.
+    ///
+    /// * `input`: the input to parse
+    pub fn parse(input: &[u8]) -> ParseResult<&[u8], HexDirection> {
+        let (len, dir) = match input {
+            [b'e', ..] => (1, HexDirection::East),
+            [b'w', ..] => (1, HexDirection::West),
+            [b's', b'e', ..] => (2, HexDirection::SouthEast),
+            [b's', b'w', ..] => (2, HexDirection::SouthWest),
+            [b'n', b'w', ..] => (2, HexDirection::NorthWest),
+            [b'n', b'e', ..] => (2, HexDirection::NorthEast),
+            _ => return None,
+        };
+        Some((&input[len..], dir))
+    }
+
+    /// Parse a run of directions without separators such as `esenee`.
+    ///
+    /// * `input`: the input to parse
+    pub fn parse_all(mut input: &[u8]) -> ParseResult<&[u8], Vec<HexDirection>> {
+        let mut dirs = Vec::new();
+        while let Some((rest, dir)) = HexDirection::parse(input) {
+            dirs.push(dir);
+            input = rest;
+        }
+        Some((input, dirs))
+    }
+
+    /// The unit step in this direction.
+    pub fn offset<T>(self) -> Hex<T>
+    where
+        T: Integer + Signed + Copy,
+    {
+        let (zero, one) = (T::zero(), T::one());
+        match self {
+            HexDirection::East => Hex::new(one, zero),
+            HexDirection::SouthEast => Hex::new(zero, one),
+            HexDirection::SouthWest => Hex::new(-one, one),
+            HexDirection::West => Hex::new(-one, zero),
+            HexDirection::NorthWest => Hex::new(zero, -one),
+            HexDirection::NorthEast => Hex::new(one, -one),
         }
     }
 }
 
-impl fmt::Display for Point3D {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+impl<T> Hex<T>
+where
+    T: Integer + Signed + Copy,
+{
+    pub fn new(q: T, r: T) -> Self {
+        Hex { q, r }
+    }
+
+    /// The third cube coordinate.
+    pub fn s(&self) -> T {
+        -self.q - self.r
+    }
+
+    /// Number of steps between `self` and `other`.
+    pub fn distance(&self, other: Hex<T>) -> T {
+        let d = *self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / (T::one() + T::one())
+    }
+
+    pub fn neighbor(&self, dir: HexDirection) -> Hex<T> {
+        *self + dir.offset()
+    }
+
+    /// Iterate over the six neighbors, clockwise starting east.
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex<T>> {
+        let hex = *self;
+        HexDirection::ALL
+            .into_iter()
+            .map(move |dir| hex.neighbor(dir))
+    }
+
+    /// Iterate over the hexagons at distance `radius`, clockwise starting at the west corner.
+    pub fn ring(&self, radius: usize) -> impl Iterator<Item = Hex<T>> {
+        let start = (0..radius).fold(*self, |hex, _| hex.neighbor(HexDirection::West));
+        let center = (radius == 0).then_some(*self);
+        // Walk along the six edges, each `radius` steps long, starting north-east.
+        let steps =
+            (0..6).flat_map(move |i| std::iter::repeat_n(HexDirection::ALL[(i + 5) % 6], radius));
+        let edges = steps.scan(start, |hex, dir| {
+            let current = *hex;
+            *hex = hex.neighbor(dir);
+            Some(current)
+        });
+        center.into_iter().chain(edges)
+    }
+
+    /// Iterate over all hexagons within distance `radius`, ring by ring starting at `self`.
+    pub fn spiral(&self, radius: usize) -> impl Iterator<Item = Hex<T>> {
+        let hex = *self;
+        (0..=radius).flat_map(move |r| hex.ring(r))
+    }
+
+    /// Convert to offset coordinates where odd rows are shifted right by half a hexagon.
+    pub fn to_offset(&self) -> Point2D<T> {
+        let two = T::one() + T::one();
+        let shift = if self.r.is_odd() { T::one() } else { T::zero() };
+        Point2D::new(self.q + (self.r - shift) / two, self.r)
+    }
+
+    /// Inverse of [`Hex::to_offset`].
+    pub fn from_offset(p: Point2D<T>) -> Self {
+        let two = T::one() + T::one();
+        let shift = if p.y.is_odd() { T::one() } else { T::zero() };
+        Hex::new(p.x - (p.y - shift) / two, p.y)
+    }
+}
+
+impl<T> ops::Add<Hex<T>> for Hex<T>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = Hex<T>;
+
+    fn add(self, rhs: Hex<T>) -> Self::Output {
+        Hex {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl<T> ops::Sub<Hex<T>> for Hex<T>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = Hex<T>;
+
+    fn sub(self, rhs: Hex<T>) -> Self::Output {
+        Hex {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+/// Compute twice the area of the closed polygon with vertices `points` using the shoelace
+/// formula. Doubling keeps the result exact for lattice polygons; the orientation of the
+/// vertices does not matter.
+pub fn polygon_area2<T>(points: &[Point2D<T>]) -> T
+where
+    T: Integer + Signed + Copy,
+{
+    let n = points.len();
+    let mut sum = T::zero();
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % n];
+        sum = sum + p.x * q.y - q.x * p.y;
+    }
+    sum.abs()
+}
+
+/// Count the lattice points on the boundary of the closed polygon with vertices `points`.
+/// For axis-aligned edges this is the length of the boundary.
+pub fn boundary_points<T>(points: &[Point2D<T>]) -> T
+where
+    T: Integer + Signed + Copy,
+{
+    let n = points.len();
+    let mut sum = T::zero();
+    for (i, p) in points.iter().enumerate() {
+        let d = points[(i + 1) % n] - *p;
+        sum = sum + d.x.gcd(&d.y);
+    }
+    sum
+}
+
+/// Count the lattice points strictly inside the closed polygon with vertices `points`, using
+/// Pick's theorem `A = i + b/2 - 1`.
+pub fn interior_points<T>(points: &[Point2D<T>]) -> T
+where
+    T: Integer + Signed + Copy,
+{
+    let two = T::one() + T::one();
+    (polygon_area2(points) - boundary_points(points) + two) / two
+}
+
+/// Test if `p` lies inside or on the boundary of the closed polygon with vertices `points`.
+pub fn point_in_polygon<T>(p: Point2D<T>, points: &[Point2D<T>]) -> bool
+where
+    T: Integer + Signed + Copy,
+{
+    let n = points.len();
+    let mut inside = false;
+    for (i, &a) in points.iter().enumerate() {
+        let b = points[(i + 1) % n];
+        // On the boundary if collinear with the edge and within its bounding box.
+        let cross = (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+        if cross.is_zero()
+            && a.x.min(b.x) <= p.x
+            && p.x <= a.x.max(b.x)
+            && a.y.min(b.y) <= p.y
+            && p.y <= a.y.max(b.y)
+        {
+            return true;
+        }
+        // Cast a ray towards +x and count the edges crossing it, using half-open intervals in y
+        // so that vertices are not counted twice.
+        if (a.y > p.y) != (b.y > p.y) {
+            // The crossing is right of `p` if `cross` has the same sign as the edge direction.
+            let upwards = b.y > a.y;
+            if (cross > T::zero()) == upwards {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Vector arithmetic shared by the fixed-size point types, applied component-wise.
+macro_rules! impl_vector_ops {
+    ($name:ident { $($field:ident),+ }) => {
+        impl<T> $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            /// Compute the dot product of `self` and `other`.
+            pub fn dot(&self, other: $name<T>) -> T {
+                T::zero() $(+ self.$field * other.$field)+
+            }
+
+            /// Component-wise minimum of `self` and `other`.
+            pub fn component_min(&self, other: $name<T>) -> Self {
+                $name { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            /// Component-wise maximum of `self` and `other`.
+            pub fn component_max(&self, other: $name<T>) -> Self {
+                $name { $($field: self.$field.max(other.$field)),+ }
+            }
+        }
+
+        impl<T> ops::Add<$name<T>> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            type Output = $name<T>;
+
+            fn add(self, rhs: $name<T>) -> Self::Output {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T> ops::Sub<$name<T>> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            type Output = $name<T>;
+
+            fn sub(self, rhs: $name<T>) -> Self::Output {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T> ops::Neg for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            type Output = $name<T>;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T> ops::Mul<T> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            type Output = $name<T>;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T> ops::AddAssign<$name<T>> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            fn add_assign(&mut self, rhs: $name<T>) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T> ops::SubAssign<$name<T>> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            fn sub_assign(&mut self, rhs: $name<T>) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T> ops::MulAssign<T> for $name<T>
+        where
+            T: Integer + Signed + Copy,
+        {
+            fn mul_assign(&mut self, rhs: T) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Point2D { x, y });
+impl_vector_ops!(Point3D { x, y, z });
+impl_vector_ops!(Point4D { x, y, z, w });
+
+/// A point with an arbitrary number of dimensions.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
+pub struct PointN<T, const N: usize>
+where
+    T: Integer + Signed + Copy,
+{
+    pub coords: [T; N],
+}
+
+impl<T, const N: usize> PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    pub fn new(coords: [T; N]) -> Self {
+        PointN { coords }
+    }
+
+    /// Compute the Manhattan distance between `self` and `other`.
+    pub fn manhattan(&self, other: &PointN<T, N>) -> T {
+        self.zip_fold(other, |acc, a, b| acc + (a - b).abs())
+    }
+
+    /// Compute the Euclidean squared distance between `self` and `other`.
+    pub fn euclidean_squared(&self, other: &PointN<T, N>) -> T {
+        self.zip_fold(other, |acc, a, b| acc + (a - b) * (a - b))
+    }
+
+    /// Compute the dot product of `self` and `other`.
+    pub fn dot(&self, other: PointN<T, N>) -> T {
+        self.zip_fold(&other, |acc, a, b| acc + a * b)
+    }
+
+    /// Component-wise minimum of `self` and `other`.
+    pub fn component_min(&self, other: PointN<T, N>) -> Self {
+        self.zip_map(other, |a, b| a.min(b))
+    }
+
+    /// Component-wise maximum of `self` and `other`.
+    pub fn component_max(&self, other: PointN<T, N>) -> Self {
+        self.zip_map(other, |a, b| a.max(b))
+    }
+
+    fn zip_map<F>(&self, other: PointN<T, N>, f: F) -> Self
+    where
+        F: Fn(T, T) -> T,
+    {
+        PointN {
+            coords: core::array::from_fn(|i| f(self.coords[i], other.coords[i])),
+        }
+    }
+
+    fn zip_fold<F>(&self, other: &PointN<T, N>, f: F) -> T
+    where
+        F: Fn(T, T, T) -> T,
+    {
+        self.coords
+            .iter()
+            .zip(other.coords.iter())
+            .fold(T::zero(), |acc, (&a, &b)| f(acc, a, b))
+    }
+}
+
+impl<T, const N: usize> Default for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    fn default() -> Self {
+        PointN {
+            coords: [T::zero(); N],
+        }
+    }
+}
+
+impl<T, const N: usize> ops::Index<usize> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = T;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.coords[i]
+    }
+}
+
+impl<T, const N: usize> ops::IndexMut<usize> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        &mut self.coords[i]
+    }
+}
+
+impl<T, const N: usize> ops::Add<PointN<T, N>> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = PointN<T, N>;
+
+    fn add(self, rhs: PointN<T, N>) -> Self::Output {
+        self.zip_map(rhs, |a, b| a + b)
+    }
+}
+
+impl<T, const N: usize> ops::Sub<PointN<T, N>> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = PointN<T, N>;
+
+    fn sub(self, rhs: PointN<T, N>) -> Self::Output {
+        self.zip_map(rhs, |a, b| a - b)
+    }
+}
+
+impl<T, const N: usize> ops::Neg for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = PointN<T, N>;
+
+    fn neg(self) -> Self::Output {
+        PointN {
+            coords: self.coords.map(|a| -a),
+        }
+    }
+}
+
+impl<T, const N: usize> ops::Mul<T> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    type Output = PointN<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        PointN {
+            coords: self.coords.map(|a| a * rhs),
+        }
+    }
+}
+
+impl<T, const N: usize> ops::AddAssign<PointN<T, N>> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    fn add_assign(&mut self, rhs: PointN<T, N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T, const N: usize> ops::SubAssign<PointN<T, N>> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    fn sub_assign(&mut self, rhs: PointN<T, N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T, const N: usize> ops::MulAssign<T> for PointN<T, N>
+where
+    T: Integer + Signed + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> From<Point2D<T>> for PointN<T, 2>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: Point2D<T>) -> Self {
+        PointN::new([p.x, p.y])
+    }
+}
+
+impl<T> From<PointN<T, 2>> for Point2D<T>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: PointN<T, 2>) -> Self {
+        let [x, y] = p.coords;
+        Point2D { x, y }
+    }
+}
+
+impl<T> From<Point3D<T>> for PointN<T, 3>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: Point3D<T>) -> Self {
+        PointN::new([p.x, p.y, p.z])
+    }
+}
+
+impl<T> From<PointN<T, 3>> for Point3D<T>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: PointN<T, 3>) -> Self {
+        let [x, y, z] = p.coords;
+        Point3D { x, y, z }
+    }
+}
+
+impl<T> From<Point4D<T>> for PointN<T, 4>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: Point4D<T>) -> Self {
+        PointN::new([p.x, p.y, p.z, p.w])
+    }
+}
+
+impl<T> From<PointN<T, 4>> for Point4D<T>
+where
+    T: Integer + Signed + Copy,
+{
+    fn from(p: PointN<T, 4>) -> Self {
+        let [x, y, z, w] = p.coords;
+        Point4D { x, y, z, w }
     }
 }
 
@@ -142,4 +900,198 @@ mod tests {
         let p = Point2D::new(0, 0);
         assert_eq!(500, p.euclidean_squared(Point2D { x: 10, y: 20 }));
     }
+
+    #[test]
+    fn test_vector_ops() {
+        let mut p = Point3D::new(1, -2, 3);
+        let q = Point3D::new(4, 5, -6);
+        assert_eq!(Point3D::new(-1, 2, -3), -p);
+        assert_eq!(Point3D::new(2, -4, 6), p * 2);
+        assert_eq!(4 - 10 - 18, p.dot(q));
+        assert_eq!(Point3D::new(1, -2, -6), p.component_min(q));
+        assert_eq!(Point3D::new(4, 5, 3), p.component_max(q));
+        p += q;
+        assert_eq!(Point3D::new(5, 3, -3), p);
+        p -= q;
+        p *= -1;
+        assert_eq!(Point3D::new(-1, 2, -3), p);
+
+        let p: Point2D<i32> = Point2D::new(1, 2);
+        assert_eq!(Point2D::new(3, 6), p * 3);
+        assert_eq!(
+            4,
+            Point4D::new(0, 0, 0, 0).manhattan(&Point4D::new(1, -1, 1, -1))
+        );
+        assert_eq!(
+            30,
+            Point4D::new(1, 2, 3, 4).euclidean_squared(&Point4D::new(0, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_point_n() {
+        let p = PointN::new([1, 2, 3, 4, 5]);
+        let q = PointN::new([1, 1, 1, 1, 1]);
+        assert_eq!(PointN::new([2, 3, 4, 5, 6]), p + q);
+        assert_eq!(15, p.dot(q));
+        assert_eq!(10, p.manhattan(&q));
+        assert_eq!(PointN::default(), p - p);
+        assert_eq!(-3, (-p)[2]);
+
+        let p4 = Point4D::new(1, 2, 3, 4);
+        assert_eq!(p4, Point4D::from(PointN::from(p4)));
+        assert_eq!(PointN::new([2, 4]), PointN::from(Point2D::new(1, 2) * 2));
+    }
+
+    #[test]
+    fn test_point3d_chebyshev_cross() {
+        let p = Point3D::new(1, 2, 3);
+        assert_eq!(5, p.chebyshev(&Point3D::new(-4, 0, 6)));
+        let x = Point3D::new(1, 0, 0);
+        let y = Point3D::new(0, 1, 0);
+        assert_eq!(Point3D::new(0, 0, 1), x.cross(&y));
+        assert_eq!(Point3D::new(0, 0, -1), y.cross(&x));
+    }
+
+    #[test]
+    fn test_point3d_neighbors() {
+        let p = Point3D::new(1, 1, 1);
+        let n6: Vec<Point3D> = p.neighbors6().collect();
+        assert_eq!(6, n6.len());
+        assert!(n6.iter().all(|q| p.manhattan(q) == 1));
+        let n26: Vec<Point3D> = p.neighbors26().collect();
+        assert_eq!(26, n26.len());
+        assert!(n26.iter().all(|q| p.chebyshev(q) == 1));
+        assert!(!n26.contains(&p));
+    }
+
+    #[test]
+    fn test_point2d_rotations() {
+        let p = Point2D::new(2, -1);
+        assert_eq!(Point2D::new(1, 2), p.rotate_right());
+        assert_eq!(Point2D::new(-1, -2), p.rotate_left());
+        assert_eq!(p.rotate_left(), p.rotate(-1));
+        assert_eq!(p.rotate_left(), p.rotate(3));
+        assert_eq!(-p, p.rotate(2));
+        assert_eq!(p, p.rotate(4));
+        assert_eq!(Point2D::new(3, 2), p.rotate_around(Point2D::new(1, 1), 1));
+        assert_eq!(Point2D::new(-2, -1), p.reflect_x());
+        assert_eq!(Point2D::new(2, 1), p.reflect_y());
+    }
+
+    #[test]
+    fn test_orientations() {
+        let p = Point3D::new(1, 2, 3);
+        let all: Vec<Orientation> = Orientation::all().collect();
+        assert_eq!(24, all.len());
+        assert_eq!(Orientation::IDENTITY, all[0]);
+        let mut images: Vec<Point3D> = all.iter().map(|o| o.apply(p)).collect();
+        images.sort();
+        images.dedup();
+        assert_eq!(24, images.len());
+
+        for a in &all {
+            assert_eq!(p, a.inverse().apply(a.apply(p)));
+            assert_eq!(Orientation::IDENTITY, a.compose(&a.inverse()));
+            for b in &all {
+                assert_eq!(a.apply(b.apply(p)), a.compose(b).apply(p));
+                assert!(all.contains(&a.compose(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_parse() {
+        let (rest, dirs) = HexDirection::parse_all(b"esenee\n").unwrap();
+        assert_eq!(b"\n", rest);
+        assert_eq!(
+            vec![
+                HexDirection::East,
+                HexDirection::SouthEast,
+                HexDirection::NorthEast,
+                HexDirection::East
+            ],
+            dirs
+        );
+        let end = dirs.into_iter().fold(Hex::default(), |h, d| h.neighbor(d));
+        assert_eq!(Hex::new(3, 0), end);
+        assert_eq!(None, HexDirection::parse(b"n"));
+    }
+
+    #[test]
+    fn test_hex_distance_and_rings() {
+        let center: Hex = Hex::new(1, -1);
+        assert_eq!(3, center.distance(Hex::new(-2, 0)));
+        assert_eq!(vec![center], center.ring(0).collect::<Vec<_>>());
+        for radius in 1..4 {
+            let ring: Vec<Hex> = center.ring(radius).collect();
+            assert_eq!(6 * radius, ring.len());
+            assert!(ring.iter().all(|h| h.distance(center) == radius as i64));
+        }
+        let mut spiral: Vec<Hex> = center.spiral(2).collect();
+        assert_eq!(19, spiral.len());
+        spiral.sort();
+        spiral.dedup();
+        assert_eq!(19, spiral.len());
+    }
+
+    #[test]
+    fn test_hex_offset() {
+        for hex in Hex::<i64>::default().spiral(3) {
+            assert_eq!(hex, Hex::from_offset(hex.to_offset()));
+        }
+        // Odd rows are shifted right, so the hexagons next to (0, 0) in rows 1 and -1 are in
+        // columns -1 and 0.
+        assert_eq!(Point2D::new(0, 1), Hex::new(0, 1).to_offset());
+        assert_eq!(Point2D::new(-1, 1), Hex::new(-1, 1).to_offset());
+        assert_eq!(Point2D::new(-1, -1), Hex::new(0, -1).to_offset());
+    }
+
+    #[test]
+    fn test_polygon() {
+        // 4x3 rectangle with a notch cut into the top edge.
+        let polygon: Vec<Point2D> = [
+            (0, 0),
+            (1, 0),
+            (1, 1),
+            (2, 1),
+            (2, 0),
+            (4, 0),
+            (4, 3),
+            (0, 3),
+        ]
+        .into_iter()
+        .map(|(x, y)| Point2D::new(x, y))
+        .collect();
+        assert_eq!(22, polygon_area2(&polygon));
+        assert_eq!(16, boundary_points(&polygon));
+        assert_eq!(4, interior_points(&polygon));
+
+        let triangle = [Point2D::new(0, 0), Point2D::new(4, 0), Point2D::new(0, 4)];
+        assert_eq!(16, polygon_area2(&triangle));
+        assert_eq!(12, boundary_points(&triangle));
+        assert_eq!(3, interior_points(&triangle));
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        let square = [
+            Point2D::new(0, 0),
+            Point2D::new(4, 0),
+            Point2D::new(4, 4),
+            Point2D::new(0, 4),
+        ];
+        assert!(point_in_polygon(Point2D::new(2, 2), &square));
+        assert!(point_in_polygon(Point2D::new(4, 1), &square));
+        assert!(point_in_polygon(Point2D::new(0, 0), &square));
+        assert!(!point_in_polygon(Point2D::new(5, 2), &square));
+        assert!(!point_in_polygon(Point2D::new(-1, 0), &square));
+
+        let triangle = [Point2D::new(0, 0), Point2D::new(4, 0), Point2D::new(0, 4)];
+        let inside = (-1..6)
+            .flat_map(|x| (-1..6).map(move |y| Point2D::new(x, y)))
+            .filter(|&p| point_in_polygon(p, &triangle))
+            .count();
+        assert_eq!(15, inside);
+    }
 }
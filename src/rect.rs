@@ -0,0 +1,317 @@
+use num_integer::Integer;
+use num_traits::{PrimInt, Signed};
+
+use crate::interval::{ClosedInterval, IntervalBox};
+use crate::point::{Point2D, Point3D};
+
+/// An axis-aligned rectangle of lattice points, one closed interval per axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T = i64>
+where
+    T: PrimInt + Integer + Signed,
+{
+    pub x: ClosedInterval<T>,
+    pub y: ClosedInterval<T>,
+}
+
+impl<T> Rect<T>
+where
+    T: PrimInt + Integer + Signed,
+{
+    pub fn new(x: ClosedInterval<T>, y: ClosedInterval<T>) -> Self {
+        Rect { x, y }
+    }
+
+    /// Create the smallest rectangle containing both corners.
+    pub fn from_corners(a: Point2D<T>, b: Point2D<T>) -> Self {
+        Rect {
+            x: ClosedInterval::new(a.x.min(b.x), a.x.max(b.x)),
+            y: ClosedInterval::new(a.y.min(b.y), a.y.max(b.y)),
+        }
+    }
+
+    /// Corner with the smallest coordinates.
+    pub fn min(&self) -> Point2D<T> {
+        Point2D::new(self.x.a, self.y.a)
+    }
+
+    /// Corner with the largest coordinates.
+    pub fn max(&self) -> Point2D<T> {
+        Point2D::new(self.x.b, self.y.b)
+    }
+
+    /// Test if `p` lies within the rectangle.
+    pub fn contains(&self, p: Point2D<T>) -> bool {
+        self.x.contains_point(p.x) && self.y.contains_point(p.y)
+    }
+
+    /// Intersect with rectangle.
+    pub fn intersect(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        Some(Rect {
+            x: self.x.intersect(&other.x)?,
+            y: self.y.intersect(&other.y)?,
+        })
+    }
+
+    /// Count the lattice points in the rectangle.
+    pub fn area(&self) -> usize {
+        self.x.len() * self.y.len()
+    }
+
+    /// Split the part of `self` not covered by `other` into at most 4 disjoint rectangles.
+    pub fn subtract(&self, other: &Rect<T>) -> Vec<Rect<T>> {
+        let Some(common) = self.intersect(other) else {
+            return vec![*self];
+        };
+        let mut pieces = Vec::with_capacity(4);
+        let (left, right) = remainder(&self.x, &common.x);
+        pieces.extend(
+            [left, right]
+                .into_iter()
+                .flatten()
+                .map(|x| Rect { x, ..*self }),
+        );
+        let (below, above) = remainder(&self.y, &common.y);
+        pieces.extend(
+            [below, above]
+                .into_iter()
+                .flatten()
+                .map(|y| Rect { x: common.x, y }),
+        );
+        pieces
+    }
+
+    /// Iterate over the lattice points, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2D<T>> {
+        let x = self.x;
+        self.y
+            .iter()
+            .flat_map(move |py| x.iter().map(move |px| Point2D::new(px, py)))
+    }
+}
+
+/// An axis-aligned cuboid of lattice points, one closed interval per axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T = i64>
+where
+    T: PrimInt + Integer + Signed,
+{
+    pub x: ClosedInterval<T>,
+    pub y: ClosedInterval<T>,
+    pub z: ClosedInterval<T>,
+}
+
+impl<T> Cuboid<T>
+where
+    T: PrimInt + Integer + Signed,
+{
+    pub fn new(x: ClosedInterval<T>, y: ClosedInterval<T>, z: ClosedInterval<T>) -> Self {
+        Cuboid { x, y, z }
+    }
+
+    /// Create the smallest cuboid containing both corners.
+    pub fn from_corners(a: Point3D<T>, b: Point3D<T>) -> Self {
+        Cuboid {
+            x: ClosedInterval::new(a.x.min(b.x), a.x.max(b.x)),
+            y: ClosedInterval::new(a.y.min(b.y), a.y.max(b.y)),
+            z: ClosedInterval::new(a.z.min(b.z), a.z.max(b.z)),
+        }
+    }
+
+    /// Corner with the smallest coordinates.
+    pub fn min(&self) -> Point3D<T> {
+        Point3D::new(self.x.a, self.y.a, self.z.a)
+    }
+
+    /// Corner with the largest coordinates.
+    pub fn max(&self) -> Point3D<T> {
+        Point3D::new(self.x.b, self.y.b, self.z.b)
+    }
+
+    /// Test if `p` lies within the cuboid.
+    pub fn contains(&self, p: Point3D<T>) -> bool {
+        self.x.contains_point(p.x) && self.y.contains_point(p.y) && self.z.contains_point(p.z)
+    }
+
+    /// Intersect with cuboid.
+    pub fn intersect(&self, other: &Cuboid<T>) -> Option<Cuboid<T>> {
+        Some(Cuboid {
+            x: self.x.intersect(&other.x)?,
+            y: self.y.intersect(&other.y)?,
+            z: self.z.intersect(&other.z)?,
+        })
+    }
+
+    /// Count the lattice points in the cuboid.
+    pub fn volume(&self) -> usize {
+        self.x.len() * self.y.len() * self.z.len()
+    }
+
+    /// Split the part of `self` not covered by `other` into at most 6 disjoint cuboids.
+    pub fn subtract(&self, other: &Cuboid<T>) -> Vec<Cuboid<T>> {
+        let Some(common) = self.intersect(other) else {
+            return vec![*self];
+        };
+        let mut pieces = Vec::with_capacity(6);
+        let (left, right) = remainder(&self.x, &common.x);
+        pieces.extend(
+            [left, right]
+                .into_iter()
+                .flatten()
+                .map(|x| Cuboid { x, ..*self }),
+        );
+        let (below, above) = remainder(&self.y, &common.y);
+        pieces.extend([below, above].into_iter().flatten().map(|y| Cuboid {
+            x: common.x,
+            y,
+            z: self.z,
+        }));
+        let (front, back) = remainder(&self.z, &common.z);
+        pieces.extend([front, back].into_iter().flatten().map(|z| Cuboid {
+            x: common.x,
+            y: common.y,
+            z,
+        }));
+        pieces
+    }
+
+    /// Iterate over the lattice points, `x` varying fastest.
+    pub fn points(&self) -> impl Iterator<Item = Point3D<T>> {
+        let Cuboid { x, y, z } = *self;
+        z.iter().flat_map(move |pz| {
+            y.iter()
+                .flat_map(move |py| x.iter().map(move |px| Point3D::new(px, py, pz)))
+        })
+    }
+}
+
+impl<T> From<Rect<T>> for IntervalBox<2, T>
+where
+    T: PrimInt + Integer + Signed,
+{
+    fn from(r: Rect<T>) -> Self {
+        IntervalBox::new([r.x, r.y])
+    }
+}
+
+impl<T> From<Cuboid<T>> for IntervalBox<3, T>
+where
+    T: PrimInt + Integer + Signed,
+{
+    fn from(c: Cuboid<T>) -> Self {
+        IntervalBox::new([c.x, c.y, c.z])
+    }
+}
+
+/// Compute the smallest rectangle containing all `points`, or `None` if there are none.
+pub fn bounding_box<T, I>(points: I) -> Option<Rect<T>>
+where
+    T: PrimInt + Integer + Signed,
+    I: IntoIterator<Item = Point2D<T>>,
+{
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), p| {
+        (min.component_min(p), max.component_max(p))
+    });
+    Some(Rect::from_corners(min, max))
+}
+
+/// Compute the smallest cuboid containing all `points`, or `None` if there are none.
+pub fn bounding_cuboid<T, I>(points: I) -> Option<Cuboid<T>>
+where
+    T: PrimInt + Integer + Signed,
+    I: IntoIterator<Item = Point3D<T>>,
+{
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), p| {
+        (min.component_min(p), max.component_max(p))
+    });
+    Some(Cuboid::from_corners(min, max))
+}
+
+/// The parts of `outer` below and above `inner`, which must be a subset of `outer`.
+fn remainder<T: PrimInt>(
+    outer: &ClosedInterval<T>,
+    inner: &ClosedInterval<T>,
+) -> (Option<ClosedInterval<T>>, Option<ClosedInterval<T>>) {
+    let below = (outer.a < inner.a).then(|| ClosedInterval::new(outer.a, inner.a - T::one()));
+    let above = (inner.b < outer.b).then(|| ClosedInterval::new(inner.b + T::one(), outer.b));
+    (below, above)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let r = Rect::from_corners(Point2D::new(3, 4), Point2D::new(0, 1));
+        assert_eq!(Point2D::new(0, 1), r.min());
+        assert_eq!(16, r.area());
+        assert_eq!(16, r.points().count());
+        assert!(r.contains(Point2D::new(3, 1)));
+        assert!(!r.contains(Point2D::new(3, 5)));
+
+        let s = Rect::from_corners(Point2D::new(2, 2), Point2D::new(5, 3));
+        assert_eq!(
+            Some(Rect::from_corners(Point2D::new(2, 2), Point2D::new(3, 3))),
+            r.intersect(&s)
+        );
+        let pieces = r.subtract(&s);
+        assert_eq!(3, pieces.len());
+        assert_eq!(12, pieces.iter().map(|p| p.area()).sum::<usize>());
+        for p in r.points() {
+            let covered = pieces.iter().filter(|piece| piece.contains(p)).count();
+            assert_eq!(usize::from(!s.contains(p)), covered);
+        }
+        assert_eq!(
+            vec![r],
+            r.subtract(&Rect::from_corners(Point2D::new(9, 9), Point2D::new(9, 9)))
+        );
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let c = Cuboid::from_corners(Point3D::new(0, 0, 0), Point3D::new(2, 2, 2));
+        let hole = Cuboid::from_corners(Point3D::new(1, 1, 1), Point3D::new(1, 1, 1));
+        assert_eq!(27, c.volume());
+        let pieces = c.subtract(&hole);
+        assert_eq!(6, pieces.len());
+        assert_eq!(26, pieces.iter().map(|p| p.volume()).sum::<usize>());
+        assert!(pieces.iter().all(|p| p.intersect(&hole).is_none()));
+        assert_eq!(27, c.points().filter(|&p| c.contains(p)).count());
+        assert_eq!(27, IntervalBox::from(c).volume());
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [Point2D::new(1, -2), Point2D::new(-3, 4), Point2D::new(0, 0)];
+        assert_eq!(
+            Some(Rect::from_corners(Point2D::new(-3, -2), Point2D::new(1, 4))),
+            bounding_box(points)
+        );
+        assert_eq!(None, bounding_box(Vec::<Point2D>::new()));
+        assert_eq!(
+            Some(Cuboid::from_corners(
+                Point3D::new(0, 0, 0),
+                Point3D::new(1, 2, 3)
+            )),
+            bounding_cuboid([Point3D::new(0, 2, 0), Point3D::new(1, 0, 3)])
+        );
+    }
+
+    #[test]
+    fn test_generic() {
+        let r: Rect<i32> = Rect::from_corners(Point2D::new(-1, 0), Point2D::new(1, 2));
+        assert_eq!(9, r.points().count());
+        assert!(r.contains(Point2D::new(0, 2)));
+        assert_eq!(
+            Some(r),
+            bounding_box([Point2D::new(1, 0), Point2D::new(-1, 2)])
+        );
+        let c: Cuboid<i8> = Cuboid::from_corners(Point3D::new(0, 0, 0), Point3D::new(1, 1, 1));
+        assert_eq!(8, IntervalBox::from(c).volume());
+    }
+}
@@ -1,3 +1,9 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use num_traits::Zero;
+
 /// Search for `needle` in `haystack`.
 pub fn search_haystack<T: PartialEq>(needle: &[T], haystack: &[T]) -> Option<usize> {
     if needle.is_empty() {
@@ -12,9 +18,389 @@ pub fn search_haystack<T: PartialEq>(needle: &[T], haystack: &[T]) -> Option<usi
         .position(|subslice| subslice == needle)
 }
 
+/// Find a shortest path from `start` to a state satisfying `success` using Dijkstra's
+/// algorithm. `successors` yields the neighbors of a state together with the (non-negative)
+/// cost of moving there.
+///
+/// Returns the path, including `start` and the goal, and its total cost.
+pub fn dijkstra<S, C, FN, IN, FS>(start: S, successors: FN, success: FS) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| C::zero(), success)
+}
+
+/// Find a shortest path from `start` to a state satisfying `success` using A*. `heuristic`
+/// estimates the remaining cost to a goal and must never overestimate it, e.g.
+/// `Point2D::manhattan` on a 4-connected grid with unit costs.
+///
+/// Returns the path, including `start` and the goal, and its total cost.
+pub fn astar<S, C, FN, IN, FH, FS>(
+    start: S,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FS: FnMut(&S) -> bool,
+{
+    // States are numbered in order of discovery so that the heap does not need `S: Ord`.
+    let mut index: HashMap<S, usize> = HashMap::new();
+    let mut nodes: Vec<(S, C, usize)> = Vec::new();
+    let mut heap = BinaryHeap::new();
+    index.insert(start.clone(), 0);
+    heap.push(Reverse((heuristic(&start), C::zero(), 0)));
+    nodes.push((start, C::zero(), usize::MAX));
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > nodes[i].1 {
+            // A cheaper way to this state was found after this entry had been pushed.
+            continue;
+        }
+        if success(&nodes[i].0) {
+            let path = trace_path(i, |j| (nodes[j].0.clone(), nodes[j].2));
+            return Some((path, cost));
+        }
+        for (next, step) in successors(&nodes[i].0) {
+            let next_cost = cost + step;
+            let j = match index.get(&next) {
+                Some(&j) if nodes[j].1 <= next_cost => continue,
+                Some(&j) => {
+                    nodes[j].1 = next_cost;
+                    nodes[j].2 = i;
+                    j
+                }
+                None => {
+                    index.insert(next.clone(), nodes.len());
+                    nodes.push((next, next_cost, i));
+                    nodes.len() - 1
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(&nodes[j].0), next_cost, j)));
+        }
+    }
+    None
+}
+
+/// Compute the cost of a shortest path from `start` to every reachable state using Dijkstra's
+/// algorithm, together with the predecessor of each state on such a path (`None` for `start`).
+/// Use [`build_path`] to reconstruct a path.
+pub fn dijkstra_all<S, C, FN, IN>(start: S, mut successors: FN) -> HashMap<S, (C, Option<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+{
+    let mut nodes: Vec<(S, C, usize)> = Vec::new();
+    let mut index: HashMap<S, usize> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    index.insert(start.clone(), 0);
+    nodes.push((start, C::zero(), usize::MAX));
+    heap.push(Reverse((C::zero(), 0)));
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if cost > nodes[i].1 {
+            continue;
+        }
+        for (next, step) in successors(&nodes[i].0) {
+            let next_cost = cost + step;
+            match index.get(&next) {
+                Some(&j) if nodes[j].1 <= next_cost => {}
+                Some(&j) => {
+                    nodes[j].1 = next_cost;
+                    nodes[j].2 = i;
+                    heap.push(Reverse((next_cost, j)));
+                }
+                None => {
+                    index.insert(next.clone(), nodes.len());
+                    heap.push(Reverse((next_cost, nodes.len())));
+                    nodes.push((next, next_cost, i));
+                }
+            }
+        }
+    }
+    let parents: Vec<Option<S>> = nodes
+        .iter()
+        .map(|&(_, _, p)| (p != usize::MAX).then(|| nodes[p].0.clone()))
+        .collect();
+    nodes
+        .into_iter()
+        .zip(parents)
+        .map(|((state, cost, _), parent)| (state, (cost, parent)))
+        .collect()
+}
+
+/// Reconstruct the path from the start state to `target` from the result of [`dijkstra_all`].
+/// Returns an empty path if `target` is unreachable.
+pub fn build_path<S, C>(target: &S, parents: &HashMap<S, (C, Option<S>)>) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut path = Vec::new();
+    let mut current = parents.get(target).map(|_| target.clone());
+    while let Some(state) = current {
+        current = parents.get(&state).and_then(|(_, parent)| parent.clone());
+        path.push(state);
+    }
+    path.reverse();
+    path
+}
+
+/// Find a shortest path (in number of steps) from `start` to a state satisfying `success`
+/// using breadth-first search. Every state is visited at most once. With a `max_depth`, paths
+/// longer than that many steps are not considered.
+///
+/// Returns the path, including `start` and the goal.
+pub fn bfs<S, FN, IN, FS>(
+    start: S,
+    mut successors: FN,
+    mut success: FS,
+    max_depth: Option<usize>,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> bool,
+{
+    // Each node stores its state and the index of its parent.
+    let mut index: HashMap<S, usize> = HashMap::new();
+    let mut nodes: Vec<(S, usize)> = Vec::new();
+    let mut queue = VecDeque::new();
+    index.insert(start.clone(), 0);
+    nodes.push((start, usize::MAX));
+    queue.push_back((0, 0));
+    while let Some((i, depth)) = queue.pop_front() {
+        if success(&nodes[i].0) {
+            return Some(trace_path(i, |j| nodes[j].clone()));
+        }
+        if max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        for next in successors(&nodes[i].0) {
+            if !index.contains_key(&next) {
+                index.insert(next.clone(), nodes.len());
+                queue.push_back((nodes.len(), depth + 1));
+                nodes.push((next, i));
+            }
+        }
+    }
+    None
+}
+
+/// Compute the number of steps from `start` to every reachable state using breadth-first
+/// search. States further than `max_depth` steps away are not explored.
+pub fn bfs_depths<S, FN, IN>(
+    start: S,
+    mut successors: FN,
+    max_depth: Option<usize>,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut depths = HashMap::new();
+    let mut queue = VecDeque::new();
+    depths.insert(start.clone(), 0);
+    queue.push_back((start, 0));
+    while let Some((state, depth)) = queue.pop_front() {
+        if max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        for next in successors(&state) {
+            if !depths.contains_key(&next) {
+                depths.insert(next.clone(), depth + 1);
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    depths
+}
+
+/// Find a path from `start` to a state satisfying `success` using depth-first search. The
+/// path is not necessarily the shortest one. With a `max_depth`, paths longer than that many
+/// steps are not considered, and a state is revisited if it is reached in fewer steps.
+///
+/// Returns the path, including `start` and the goal.
+pub fn dfs<S, FN, IN, FS>(
+    start: S,
+    mut successors: FN,
+    mut success: FS,
+    max_depth: Option<usize>,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> bool,
+{
+    if success(&start) {
+        return Some(vec![start]);
+    }
+    // Deeper levels check the limit before expanding, the root has to do it here.
+    if max_depth == Some(0) {
+        return None;
+    }
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    seen.insert(start.clone(), 0);
+    let mut stack = vec![successors(&start).into_iter()];
+    let mut path = vec![start];
+    while let Some(iter) = stack.last_mut() {
+        let Some(next) = iter.next() else {
+            stack.pop();
+            path.pop();
+            continue;
+        };
+        let depth = path.len();
+        if seen
+            .get(&next)
+            .is_some_and(|&d| max_depth.is_none() || d <= depth)
+        {
+            continue;
+        }
+        if success(&next) {
+            path.push(next);
+            return Some(path);
+        }
+        seen.insert(next.clone(), depth);
+        if max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        stack.push(successors(&next).into_iter());
+        path.push(next);
+    }
+    None
+}
+
+/// Find a shortest path from `start` to `goal` by searching forward from `start` with
+/// `successors` and backward from `goal` with `predecessors` until both searches meet. For
+/// undirected graphs both functions are the same.
+///
+/// Returns the path, including `start` and `goal`.
+pub fn bidirectional_bfs<S, FN, IN, FP, IP>(
+    start: S,
+    goal: S,
+    mut successors: FN,
+    mut predecessors: FP,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FP: FnMut(&S) -> IP,
+    IP: IntoIterator<Item = S>,
+{
+    if start == goal {
+        return Some(vec![start]);
+    }
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+    forward.insert(start.clone(), (0, None));
+    backward.insert(goal.clone(), (0, None));
+    let mut forward_frontier = vec![start];
+    let mut backward_frontier = vec![goal];
+    let meeting = loop {
+        if forward_frontier.is_empty() || backward_frontier.is_empty() {
+            return None;
+        }
+        // Always grow the smaller frontier by one full layer.
+        let meeting = if forward_frontier.len() <= backward_frontier.len() {
+            expand_layer(
+                &mut forward_frontier,
+                &mut forward,
+                &backward,
+                &mut successors,
+            )
+        } else {
+            expand_layer(
+                &mut backward_frontier,
+                &mut backward,
+                &forward,
+                &mut predecessors,
+            )
+        };
+        if let Some(meeting) = meeting {
+            break meeting;
+        }
+    };
+    let mut path = Vec::new();
+    let mut current = Some(meeting.clone());
+    while let Some(state) = current {
+        current = forward[&state].1.clone();
+        path.push(state);
+    }
+    path.reverse();
+    let mut current = backward[&meeting].1.clone();
+    while let Some(state) = current {
+        current = backward[&state].1.clone();
+        path.push(state);
+    }
+    Some(path)
+}
+
+/// Expand every state of `frontier` by one step, replacing it with the newly discovered
+/// states. Returns the discovered state lying on the shortest path through both searches, if
+/// any state was also reached by `other`.
+fn expand_layer<S, FN, IN>(
+    frontier: &mut Vec<S>,
+    seen: &mut HashMap<S, (usize, Option<S>)>,
+    other: &HashMap<S, (usize, Option<S>)>,
+    neighbors: &mut FN,
+) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut best: Option<(usize, S)> = None;
+    for state in std::mem::take(frontier) {
+        let depth = seen[&state].0 + 1;
+        for next in neighbors(&state) {
+            if seen.contains_key(&next) {
+                continue;
+            }
+            if let Some(&(other_depth, _)) = other.get(&next) {
+                if best
+                    .as_ref()
+                    .is_none_or(|(len, _)| depth + other_depth < *len)
+                {
+                    best = Some((depth + other_depth, next.clone()));
+                }
+            }
+            seen.insert(next.clone(), (depth, Some(state.clone())));
+            frontier.push(next);
+        }
+    }
+    best.map(|(_, state)| state)
+}
+
+/// Follow parent indices from node `i` up to the root, whose parent is `usize::MAX`, and
+/// return the visited states from the root to `i`. `node` maps an index to its state and parent.
+fn trace_path<S>(mut i: usize, mut node: impl FnMut(usize) -> (S, usize)) -> Vec<S> {
+    let mut path = Vec::new();
+    while i != usize::MAX {
+        let (state, parent) = node(i);
+        path.push(state);
+        i = parent;
+    }
+    path.reverse();
+    path
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::Grid;
+    use crate::point::Point2D;
 
     #[test]
     fn test_search_haystack_empty() {
@@ -39,4 +425,138 @@ mod tests {
         let pos = search_haystack(&needle[..], &haystack[..]);
         assert_eq!(Some(6), pos);
     }
+
+    /// Successors on a maze where `#` is a wall, moving one cell at a time.
+    fn maze_successors(maze: &Grid<u8>) -> impl Fn(&Point2D) -> Vec<(Point2D, i64)> + '_ {
+        move |&p| {
+            maze.neighbors4(p)
+                .filter(|&q| maze[q] != b'#')
+                .map(|q| (q, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_astar_grid() {
+        let maze = Grid::from_input(b"S..#....\n.#.#.##.\n.#...#E.\n.####.#.\n........\n", |b| b);
+        let start = maze.position(|&b| b == b'S').unwrap();
+        let goal = maze.position(|&b| b == b'E').unwrap();
+
+        let (path, cost) = dijkstra(start, maze_successors(&maze), |&p| p == goal).unwrap();
+        assert_eq!(14, cost);
+        assert_eq!(15, path.len());
+        assert_eq!((Some(&start), Some(&goal)), (path.first(), path.last()));
+
+        let (path, cost) = astar(
+            start,
+            maze_successors(&maze),
+            |p| p.manhattan(goal),
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(14, cost);
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert_eq!(
+            None,
+            dijkstra(start, maze_successors(&maze), |&p| p == Point2D::new(3, 0))
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        // Weighted: going around is cheaper than the direct edge.
+        let successors = |&n: &u32| match n {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let all = dijkstra_all(0, successors);
+        assert_eq!(4, all.len());
+        assert_eq!((0, None), all[&0]);
+        assert_eq!((3, Some(1)), all[&2]);
+        assert_eq!(vec![0, 1, 2, 3], build_path(&3, &all));
+        assert!(build_path(&7, &all).is_empty());
+        assert_eq!(
+            Some((vec![0, 1, 2], 3)),
+            dijkstra(0, successors, |&n| n == 2)
+        );
+    }
+
+    /// Successors in the "add one or double" puzzle.
+    fn grow(&n: &u32) -> [u32; 2] {
+        [n + 1, n * 2]
+    }
+
+    /// Predecessors in the "add one or double" puzzle.
+    fn shrink(&n: &u32) -> impl Iterator<Item = u32> {
+        n.checked_sub(1)
+            .into_iter()
+            .chain((n % 2 == 0).then_some(n / 2))
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = Grid::from_input(b"S..#....\n.#.#.##.\n.#...#E.\n.####.#.\n........\n", |b| b);
+        let start = maze.position(|&b| b == b'S').unwrap();
+        let goal = maze.position(|&b| b == b'E').unwrap();
+        let successors = |&p: &Point2D| maze.neighbors4(p).filter(|&q| maze[q] != b'#');
+
+        let path = bfs(start, successors, |&p| p == goal, None).unwrap();
+        assert_eq!(15, path.len());
+        assert_eq!((Some(&start), Some(&goal)), (path.first(), path.last()));
+        assert_eq!(
+            None,
+            bfs(start, successors, |&p| p == Point2D::new(3, 0), None)
+        );
+        assert_eq!(None, bfs(start, successors, |&p| p == goal, Some(13)));
+        assert_eq!(Some(path), bfs(start, successors, |&p| p == goal, Some(14)));
+
+        let depths = bfs_depths(start, successors, None);
+        assert_eq!(Some(&14), depths.get(&goal));
+        assert_eq!(
+            maze.iter().filter(|(_, &b)| b != b'#').count(),
+            depths.len()
+        );
+        let near = bfs_depths(start, successors, Some(2));
+        assert_eq!(5, near.len());
+        assert!(near.values().all(|&d| d <= 2));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let path = dfs(1, grow, |&n| n == 10, None).unwrap();
+        assert_eq!((Some(&1), Some(&10)), (path.first(), path.last()));
+        assert!(path.windows(2).all(|w| grow(&w[0]).contains(&w[1])));
+
+        // 1 -> 2 -> 4 -> 5 -> 10 is the only way to reach 10 within four steps.
+        assert_eq!(
+            Some(vec![1, 2, 4, 5, 10]),
+            dfs(1, grow, |&n| n == 10, Some(4))
+        );
+        assert_eq!(None, dfs(1, grow, |&n| n == 10, Some(3)));
+        assert_eq!(Some(vec![7]), dfs(7, grow, |&n| n == 7, Some(0)));
+
+        // The depth limit also applies to the successors of `start`.
+        for max_depth in [Some(0), Some(1)] {
+            assert_eq!(
+                bfs(1, grow, |&n| n == 2, max_depth),
+                dfs(1, grow, |&n| n == 2, max_depth)
+            );
+        }
+        assert_eq!(None, dfs(1, grow, |&n| n == 2, Some(0)));
+        assert_eq!(Some(vec![1, 2]), dfs(1, grow, |&n| n == 2, Some(1)));
+    }
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let path = bidirectional_bfs(1, 100, grow, shrink).unwrap();
+        assert_eq!(bfs(1, grow, |&n| n == 100, None).unwrap().len(), path.len());
+        assert_eq!((Some(&1), Some(&100)), (path.first(), path.last()));
+        assert!(path.windows(2).all(|w| grow(&w[0]).contains(&w[1])));
+
+        assert_eq!(Some(vec![5]), bidirectional_bfs(5, 5, grow, shrink));
+        assert_eq!(None, bidirectional_bfs(5, 3, grow, shrink));
+    }
 }
@@ -0,0 +1,244 @@
+use core::iter;
+
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::Signed;
+
+use crate::point::Point2D;
+
+/// A line segment between two lattice points, including both end points.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Default)]
+pub struct Segment<T = i64>
+where
+    T: Integer + Signed + Copy,
+{
+    pub start: Point2D<T>,
+    pub end: Point2D<T>,
+}
+
+impl<T> Segment<T>
+where
+    T: Integer + Signed + Copy,
+{
+    pub fn new(start: Point2D<T>, end: Point2D<T>) -> Self {
+        Segment { start, end }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// Test if the segment has a slope of exactly 1 or -1.
+    pub fn is_diagonal(&self) -> bool {
+        let d = self.end - self.start;
+        !d.x.is_zero() && d.x.abs() == d.y.abs()
+    }
+
+    /// Iterate over the lattice points on the segment, from `start` to `end`.
+    ///
+    /// For horizontal, vertical and diagonal segments these are exactly the cells the segment
+    /// covers. Use [`Segment::rasterize`] to draw arbitrary slopes.
+    pub fn points(&self) -> impl Iterator<Item = Point2D<T>> {
+        let d = self.end - self.start;
+        let n = d.x.gcd(&d.y);
+        let step = if n.is_zero() {
+            d
+        } else {
+            Point2D::new(d.x / n, d.y / n)
+        };
+        let start = self.start;
+        let mut i = T::zero();
+        iter::from_fn(move || {
+            if i > n {
+                return None;
+            }
+            let p = start + step * i;
+            i = i + T::one();
+            Some(p)
+        })
+    }
+
+    /// Iterate over the cells approximating the segment using Bresenham's line algorithm, from
+    /// `start` to `end`. Consecutive cells are 8-connected.
+    pub fn rasterize(&self) -> impl Iterator<Item = Point2D<T>> {
+        let end = self.end;
+        let dx = (end.x - self.start.x).abs();
+        let dy = -(end.y - self.start.y).abs();
+        let sx = (end.x - self.start.x).signum();
+        let sy = (end.y - self.start.y).signum();
+        let mut err = dx + dy;
+        let mut next = Some(self.start);
+        iter::from_fn(move || {
+            let p = next?;
+            if p == end {
+                next = None;
+                return Some(p);
+            }
+            let mut q = p;
+            let e2 = err + err;
+            if e2 >= dy {
+                err = err + dy;
+                q.x = q.x + sx;
+            }
+            if e2 <= dx {
+                err = err + dx;
+                q.y = q.y + sy;
+            }
+            next = Some(q);
+            Some(p)
+        })
+    }
+
+    /// Test if `p` lies on the segment.
+    pub fn contains(&self, p: Point2D<T>) -> bool {
+        cross(self.start, self.end, p).is_zero() && self.in_bounding_box(p)
+    }
+
+    /// Test if the segments have at least one point in common, including touching end points
+    /// and collinear overlaps.
+    pub fn intersects(&self, other: &Segment<T>) -> bool {
+        let d1 = cross(other.start, other.end, self.start).signum();
+        let d2 = cross(other.start, other.end, self.end).signum();
+        let d3 = cross(self.start, self.end, other.start).signum();
+        let d4 = cross(self.start, self.end, other.end).signum();
+        if d1 * d2 < T::zero() && d3 * d4 < T::zero() {
+            return true;
+        }
+        (d1.is_zero() && other.in_bounding_box(self.start))
+            || (d2.is_zero() && other.in_bounding_box(self.end))
+            || (d3.is_zero() && self.in_bounding_box(other.start))
+            || (d4.is_zero() && self.in_bounding_box(other.end))
+    }
+
+    /// Compute the point where the segments cross, as exact fractions `(x, y)`.
+    ///
+    /// Returns `None` if the segments do not meet or if they are parallel, which includes
+    /// collinear segments sharing more than one point.
+    pub fn intersection(&self, other: &Segment<T>) -> Option<(Ratio<T>, Ratio<T>)> {
+        let (mut t, mut u, mut denom) = self.crossing_parameters(other)?;
+        if denom < T::zero() {
+            (t, u, denom) = (-t, -u, -denom);
+        }
+        let within = |v: T| T::zero() <= v && v <= denom;
+        if !within(t) || !within(u) {
+            return None;
+        }
+        Some(self.point_at(t, denom))
+    }
+
+    /// Compute the point where the infinite lines through both segments cross, as exact
+    /// fractions `(x, y)`. Returns `None` for parallel lines.
+    pub fn line_intersection(&self, other: &Segment<T>) -> Option<(Ratio<T>, Ratio<T>)> {
+        let (t, _, denom) = self.crossing_parameters(other)?;
+        Some(self.point_at(t, denom))
+    }
+
+    /// Solve `start + t/denom * (end - start) = other.start + u/denom * (other.end - other.start)`.
+    fn crossing_parameters(&self, other: &Segment<T>) -> Option<(T, T, T)> {
+        let r = self.end - self.start;
+        let s = other.end - other.start;
+        let denom = r.x * s.y - r.y * s.x;
+        if denom.is_zero() {
+            return None;
+        }
+        let qp = other.start - self.start;
+        let t = qp.x * s.y - qp.y * s.x;
+        let u = qp.x * r.y - qp.y * r.x;
+        Some((t, u, denom))
+    }
+
+    fn point_at(&self, t: T, denom: T) -> (Ratio<T>, Ratio<T>) {
+        let r = self.end - self.start;
+        let t = Ratio::new(t, denom);
+        (
+            Ratio::from(self.start.x) + t * r.x,
+            Ratio::from(self.start.y) + t * r.y,
+        )
+    }
+
+    fn in_bounding_box(&self, p: Point2D<T>) -> bool {
+        let min = self.start.component_min(self.end);
+        let max = self.start.component_max(self.end);
+        min.x <= p.x && p.x <= max.x && min.y <= p.y && p.y <= max.y
+    }
+}
+
+/// Cross product of `a - o` and `b - o`; positive if `o, a, b` turn counterclockwise.
+fn cross<T>(o: Point2D<T>, a: Point2D<T>, b: Point2D<T>) -> T
+where
+    T: Integer + Signed + Copy,
+{
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(x1: i64, y1: i64, x2: i64, y2: i64) -> Segment {
+        Segment::new(Point2D::new(x1, y1), Point2D::new(x2, y2))
+    }
+
+    #[test]
+    fn test_points() {
+        let points: Vec<Point2D> = segment(9, 7, 7, 9).points().collect();
+        assert_eq!(
+            vec![Point2D::new(9, 7), Point2D::new(8, 8), Point2D::new(7, 9)],
+            points
+        );
+        assert!(segment(9, 7, 7, 9).is_diagonal());
+        assert_eq!(4, segment(0, 9, 3, 9).points().count());
+        assert_eq!(3, segment(0, 0, 4, 2).points().count());
+        assert_eq!(1, segment(1, 1, 1, 1).points().count());
+    }
+
+    #[test]
+    fn test_rasterize() {
+        let cells: Vec<Point2D> = segment(0, 0, 4, 2).rasterize().collect();
+        assert_eq!(
+            vec![
+                Point2D::new(0, 0),
+                Point2D::new(1, 1),
+                Point2D::new(2, 1),
+                Point2D::new(3, 2),
+                Point2D::new(4, 2),
+            ],
+            cells
+        );
+        let cells: Vec<Point2D> = segment(2, 5, 2, 2).rasterize().collect();
+        assert_eq!(4, cells.len());
+        assert_eq!(Some(&Point2D::new(2, 2)), cells.last());
+    }
+
+    #[test]
+    fn test_intersects() {
+        assert!(segment(0, 0, 4, 4).intersects(&segment(0, 4, 4, 0)));
+        assert!(segment(0, 0, 4, 0).intersects(&segment(4, 0, 4, 3)));
+        assert!(segment(0, 0, 4, 0).intersects(&segment(2, 0, 6, 0)));
+        assert!(!segment(0, 0, 4, 0).intersects(&segment(5, 0, 6, 0)));
+        assert!(!segment(0, 0, 4, 4).intersects(&segment(0, 1, 3, 4)));
+        assert!(segment(0, 0, 4, 4).contains(Point2D::new(3, 3)));
+        assert!(!segment(0, 0, 4, 4).contains(Point2D::new(5, 5)));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = segment(0, 0, 3, 1);
+        let b = segment(0, 1, 3, 0);
+        assert_eq!(
+            Some((Ratio::new(3, 2), Ratio::new(1, 2))),
+            a.intersection(&b)
+        );
+        assert_eq!(None, a.intersection(&segment(0, 1, 1, 2)));
+        assert_eq!(
+            Some((Ratio::new(-3, 1), Ratio::new(-1, 1))),
+            a.line_intersection(&segment(-3, -1, -3, 5))
+        );
+        assert_eq!(None, a.intersection(&segment(0, 1, 1, 1)));
+        assert_eq!(None, a.line_intersection(&segment(0, 1, 3, 2)));
+    }
+}